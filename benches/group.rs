@@ -1,7 +1,13 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use ff::Field;
 use group::prime::PrimeCurveAffine;
+use group::Group;
+use halo2curves::bn256::{
+    multi_miller_loop, pairing, Bn256, Fr, G1Affine, G2Affine, G2Prepared, G1,
+};
 use halo2curves::secp256k1::Secp256k1;
+use halo2curves::CurveEndo;
+use pairing::MillerLoopResult;
 use pasta_curves::arithmetic::CurveExt;
 use rand_core::OsRng;
 
@@ -48,5 +54,80 @@ fn criterion_benchmark<G: CurveExt>(c: &mut Criterion) {
     }
 }
 
-criterion_group!(benches, criterion_benchmark<Secp256k1>);
+fn glv_benchmark<G: CurveEndo>(c: &mut Criterion) {
+    let name = "GEndo";
+    let p = G::random(OsRng);
+    let s = G::ScalarExt::random(OsRng);
+
+    c.bench_function(&format!("{name} scalar multiplication"), move |b| {
+        b.iter(|| black_box(p) * black_box(s))
+    });
+    c.bench_function(&format!("{name} GLV scalar multiplication"), move |b| {
+        b.iter(|| black_box(p).mul_glv(black_box(&s)))
+    });
+    c.bench_function(
+        &format!("{name} GLV scalar multiplication vartime"),
+        move |b| b.iter(|| black_box(p).mul_glv_vartime(black_box(&s))),
+    );
+}
+
+fn wnaf_benchmark(c: &mut Criterion) {
+    let p = Secp256k1::random(OsRng);
+    let s = <Secp256k1 as CurveExt>::ScalarExt::random(OsRng);
+    for window in [3, 4, 5, 6] {
+        c.bench_function(
+            &format!("Secp256k1 scalar multiplication vartime w={window}"),
+            move |b| b.iter(|| black_box(p).mul_wnaf(black_box(&s), window)),
+        );
+    }
+
+    c.bench_function("Secp256k1 generator multiplication", move |b| {
+        b.iter(|| Secp256k1::generator() * black_box(s))
+    });
+    Secp256k1::generator_table();
+    c.bench_function("Secp256k1 generator multiplication fixed-base", move |b| {
+        b.iter(|| Secp256k1::mul_generator(black_box(&s)))
+    });
+
+    let gt = pairing(&G1Affine::generator(), &G2Affine::generator());
+    let s = Fr::random(OsRng);
+    c.bench_function("Gt scalar multiplication", move |b| {
+        b.iter(|| black_box(gt) * black_box(s))
+    });
+    c.bench_function("Gt scalar multiplication GLS", move |b| {
+        b.iter(|| black_box(gt).mul_gls(black_box(&s)))
+    });
+    c.bench_function("Gt scalar multiplication vartime", move |b| {
+        b.iter(|| black_box(gt).mul_vartime(black_box(&s)))
+    });
+    for window in [3, 4, 5, 6] {
+        c.bench_function(
+            &format!("Gt scalar multiplication vartime w={window}"),
+            move |b| b.iter(|| black_box(gt).mul_wnaf(black_box(&s), window)),
+        );
+    }
+
+    let p = G1Affine::generator();
+    let q = G2Prepared::from(G2Affine::generator());
+    let f = multi_miller_loop(&[(&p, &q)]);
+    c.bench_function("Bn256 final exponentiation", move |b| {
+        b.iter(|| black_box(f).final_exponentiation())
+    });
+    // e(2P, Q) e(-P, 2Q) = 1
+    let p2 = G1Affine::from(p + p);
+    let q2 = G2Prepared::from(G2Affine::from(
+        G2Affine::generator() + G2Affine::generator(),
+    ));
+    let terms = [(&p2, &q), (&-p, &q2)];
+    c.bench_function("Bn256 pairing check", move |b| {
+        b.iter(|| Bn256::pairing_check(black_box(&terms)))
+    });
+}
+
+criterion_group!(
+    benches,
+    criterion_benchmark<Secp256k1>,
+    glv_benchmark<G1>,
+    wnaf_benchmark
+);
 criterion_main!(benches);
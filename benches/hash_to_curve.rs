@@ -27,6 +27,10 @@ fn hash_to_bn256(c: &mut Criterion) {
     hash_to_curve::<halo2curves::bn256::G1>(c, "Bn256");
 }
 
+fn hash_to_bn256_g2(c: &mut Criterion) {
+    hash_to_curve::<halo2curves::bn256::G2>(c, "Bn256 G2");
+}
+
 fn hash_to_grumpkin(c: &mut Criterion) {
     hash_to_curve::<halo2curves::grumpkin::G1>(c, "Grumpkin");
 }
@@ -54,6 +58,7 @@ criterion_group!(
     hash_to_pallas,
     hash_to_vesta,
     hash_to_bn256,
+    hash_to_bn256_g2,
     hash_to_grumpkin,
 );
 criterion_main!(benches);
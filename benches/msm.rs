@@ -0,0 +1,79 @@
+//! This benchmarks Multi Scalar Multiplication (MSM).
+//! It measures `G1` from the BN256 curve, with projective and batch-affine
+//! bucket accumulation, against the naive sum of scalar multiplications.
+//! For a few terms, it compares the Straus routines with the bucket method.
+//!
+//! To run this benchmark:
+//!
+//!     cargo bench --bench msm
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use ff::Field;
+use group::{Curve, Group};
+use halo2curves::bn256::{Fr, G1Affine, G1};
+use halo2curves::msm::{msm, msm_batch_affine, straus, straus_endo};
+use rand::SeedableRng;
+use rand_xorshift::XorShiftRng;
+
+const SAMPLE_SIZE: usize = 10;
+const SINGLECORE_RANGE: [u8; 4] = [4, 8, 10, 12];
+const SEED: [u8; 16] = [
+    0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc, 0xe5,
+];
+
+fn generate_coefficients_and_curvepoints(k: u8) -> (Vec<Fr>, Vec<G1Affine>) {
+    let n: u64 = 1 << k;
+    let mut rng = XorShiftRng::from_seed(SEED);
+
+    let coeffs = (0..n).map(|_| Fr::random(&mut rng)).collect();
+    let bases = (0..n).map(|_| G1::random(&mut rng).to_affine()).collect();
+    (coeffs, bases)
+}
+
+fn msm_singlecore(c: &mut Criterion) {
+    let mut group = c.benchmark_group("msm");
+    group.sample_size(SAMPLE_SIZE);
+    for k in SINGLECORE_RANGE {
+        let (coeffs, bases) = generate_coefficients_and_curvepoints(k);
+
+        group.bench_function(BenchmarkId::new("pippenger", k), |b| {
+            b.iter(|| msm(black_box(&coeffs), black_box(&bases)))
+        });
+        group.bench_function(BenchmarkId::new("batch_affine", k), |b| {
+            b.iter(|| msm_batch_affine(black_box(&coeffs), black_box(&bases)))
+        });
+        group.bench_function(BenchmarkId::new("naive", k), |b| {
+            b.iter(|| {
+                black_box(&coeffs)
+                    .iter()
+                    .zip(black_box(&bases).iter())
+                    .fold(G1::identity(), |acc, (coeff, base)| acc + base * coeff)
+            })
+        });
+    }
+    group.finish();
+}
+
+fn msm_small(c: &mut Criterion) {
+    let mut group = c.benchmark_group("msm_small");
+    for n in [2u64, 4, 8, 16] {
+        let mut rng = XorShiftRng::from_seed(SEED);
+        let coeffs: Vec<_> = (0..n).map(|_| Fr::random(&mut rng)).collect();
+        let points: Vec<_> = (0..n).map(|_| G1::random(&mut rng)).collect();
+        let bases: Vec<_> = points.iter().map(|p| p.to_affine()).collect();
+
+        group.bench_function(BenchmarkId::new("pippenger", n), |b| {
+            b.iter(|| msm(black_box(&coeffs), black_box(&bases)))
+        });
+        group.bench_function(BenchmarkId::new("straus", n), |b| {
+            b.iter(|| straus(black_box(&coeffs), black_box(&points)))
+        });
+        group.bench_function(BenchmarkId::new("straus_endo", n), |b| {
+            b.iter(|| straus_endo(black_box(&coeffs), black_box(&points)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, msm_singlecore, msm_small);
+criterion_main!(benches);
@@ -0,0 +1,250 @@
+# This file generates the test vectors of the BN254 G2 hash-to-curve suites
+# `BN254G2_XMD:SHA-256_SVDW_RO_` and `BN254G2_XMD:SHA-256_SVDW_NU_`, and of
+# the SvdW map they use, as checked in `src/bn256/curve.rs`.
+#
+# It follows the construction of gnark-crypto's bn254 `HashToG2` and
+# `EncodeToG2`, independently of this crate:
+# - `hash_to_field` of RFC 9380 section 5.2 with `expand_message_xmd` over
+#   SHA-256, `m = 2` and `L = 48`,
+# - the straight-line SvdW map of RFC 9380 section 6.6.1 with `Z = 1`,
+# - the cofactor clearing `[x]Q + psi([3x]Q) + psi^2([x]Q) + psi^3(Q)` of
+#   https://eprint.iacr.org/2011/615.pdf, section 6.1.
+#
+# Usage: python3 script/bn254_g2_hash_vectors.py
+
+import hashlib
+import random
+
+p = 0x30644E72E131A029B85045B68181585D97816A916871CA8D3C208C16D87CFD47
+r = 0x30644E72E131A029B85045B68181585D2833E84879B9709143E1F593F0000001
+x_bn = 4965661367192848881
+
+
+class Fq2:
+    def __init__(self, c0, c1=0):
+        self.c0 = c0 % p
+        self.c1 = c1 % p
+
+    def __add__(self, o):
+        o = lift(o)
+        return Fq2(self.c0 + o.c0, self.c1 + o.c1)
+
+    __radd__ = __add__
+
+    def __sub__(self, o):
+        o = lift(o)
+        return Fq2(self.c0 - o.c0, self.c1 - o.c1)
+
+    def __rsub__(self, o):
+        return lift(o) - self
+
+    def __neg__(self):
+        return Fq2(-self.c0, -self.c1)
+
+    def __mul__(self, o):
+        o = lift(o)
+        return Fq2(
+            self.c0 * o.c0 - self.c1 * o.c1, self.c0 * o.c1 + self.c1 * o.c0
+        )
+
+    __rmul__ = __mul__
+
+    def __eq__(self, o):
+        o = lift(o)
+        return self.c0 == o.c0 and self.c1 == o.c1
+
+    def __pow__(self, e):
+        res, base = Fq2(1), self
+        while e:
+            if e & 1:
+                res = res * base
+            base = base * base
+            e >>= 1
+        return res
+
+    def __truediv__(self, o):
+        return self * lift(o).inv()
+
+    def norm(self):
+        return (self.c0 * self.c0 + self.c1 * self.c1) % p
+
+    def inv(self):
+        n = pow(self.norm(), p - 2, p)
+        return Fq2(self.c0 * n, -self.c1 * n)
+
+    def inv0(self):
+        return Fq2(0) if self == 0 else self.inv()
+
+    def conj(self):
+        return Fq2(self.c0, -self.c1)
+
+    def is_square(self):
+        return self == 0 or pow(self.norm(), (p - 1) // 2, p) == 1
+
+    def sqrt(self):
+        # Algorithm 9 of https://eprint.iacr.org/2012/685.pdf, for p = 3 mod 4.
+        a1 = self ** ((p - 3) // 4)
+        alpha = a1 * a1 * self
+        x0 = a1 * self
+        if alpha == -1:
+            return Fq2(0, 1) * x0
+        return (alpha + 1) ** ((p - 1) // 2) * x0
+
+    def sgn0(self):
+        return (self.c0 & 1) | ((self.c0 == 0) & (self.c1 & 1))
+
+    def __repr__(self):
+        return "(%s, %s)" % (hex(self.c0), hex(self.c1))
+
+
+def lift(a):
+    return a if isinstance(a, Fq2) else Fq2(a)
+
+
+# The twist `y^2 = x^3 + 3 / xi` with `xi = 9 + u`.
+xi = Fq2(9, 1)
+A, B, Z = Fq2(0), Fq2(3) / xi, Fq2(1)
+
+
+def g(x):
+    return x * x * x + A * x + B
+
+
+# Constants of RFC 9380 section 6.6.1.
+c1 = g(Z)
+c2 = -Z / 2
+c3 = (-g(Z) * (3 * Z * Z + 4 * A)).sqrt()
+if c3.sgn0():
+    c3 = -c3
+c4 = -4 * g(Z) / (3 * Z * Z + 4 * A)
+
+
+def map_to_curve(u):
+    tv1 = u * u * c1
+    tv2 = 1 + tv1
+    tv1 = 1 - tv1
+    tv3 = (tv1 * tv2).inv0()
+    tv4 = u * tv1 * tv3 * c3
+    x1 = c2 - tv4
+    x2 = c2 + tv4
+    x3 = Z + c4 * (tv2 * tv2 * tv3) * (tv2 * tv2 * tv3)
+    if g(x1).is_square():
+        x = x1
+    elif g(x2).is_square():
+        x = x2
+    else:
+        x = x3
+    y = g(x).sqrt()
+    if u.sgn0() != y.sgn0():
+        y = -y
+    return (x, y)
+
+
+def add(P, Q):
+    if P is None:
+        return Q
+    if Q is None:
+        return P
+    (x1, y1), (x2, y2) = P, Q
+    if x1 == x2:
+        if y1 != y2 or y1 == 0:
+            return None
+        lam = 3 * x1 * x1 / (2 * y1)
+    else:
+        lam = (y2 - y1) / (x2 - x1)
+    x3 = lam * lam - x1 - x2
+    return (x3, lam * (x1 - x3) - y1)
+
+
+def mul(P, k):
+    R = None
+    for bit in bin(k)[2:]:
+        R = add(R, R)
+        if bit == "1":
+            R = add(R, P)
+    return R
+
+
+def psi(P):
+    if P is None:
+        return None
+    x, y = P
+    return (x.conj() * xi ** ((p - 1) // 3), y.conj() * xi ** ((p - 1) // 2))
+
+
+def clear_cofactor(Q):
+    xQ = mul(Q, x_bn)
+    res = add(xQ, psi(mul(xQ, 3)))
+    res = add(res, psi(psi(xQ)))
+    return add(res, psi(psi(psi(Q))))
+
+
+def expand_message_xmd(msg, dst, len_in_bytes):
+    b_in_bytes, r_in_bytes = 32, 64
+    ell = (len_in_bytes + b_in_bytes - 1) // b_in_bytes
+    dst_prime = dst + bytes([len(dst)])
+    b0 = hashlib.sha256(
+        bytes(r_in_bytes) + msg + len_in_bytes.to_bytes(2, "big") + b"\0" + dst_prime
+    ).digest()
+    bi = hashlib.sha256(b0 + b"\1" + dst_prime).digest()
+    out = bi
+    for i in range(2, ell + 1):
+        xored = bytes(a ^ b for a, b in zip(b0, bi))
+        bi = hashlib.sha256(xored + bytes([i]) + dst_prime).digest()
+        out += bi
+    return out[:len_in_bytes]
+
+
+def hash_to_field(msg, dst, count):
+    L = 48
+    uniform_bytes = expand_message_xmd(msg, dst, count * 2 * L)
+    e = [
+        int.from_bytes(uniform_bytes[i * L : (i + 1) * L], "big") % p
+        for i in range(2 * count)
+    ]
+    return [Fq2(e[2 * i], e[2 * i + 1]) for i in range(count)]
+
+
+def hash_to_curve(msg, dst):
+    u0, u1 = hash_to_field(msg, dst, 2)
+    return clear_cofactor(add(map_to_curve(u0), map_to_curve(u1)))
+
+
+def encode_to_curve(msg, dst):
+    (u,) = hash_to_field(msg, dst, 1)
+    return clear_cofactor(map_to_curve(u))
+
+
+def check(P):
+    x, y = P
+    assert y * y == g(x)
+    assert mul(P, r) is None
+
+
+if __name__ == "__main__":
+    print("map constants")
+    for c in (c1, c2, c3, c4):
+        print(" ", c)
+
+    # `1 - c1 * u^2 = 0`, where `inv0` returns zero, then fixed and random inputs.
+    exceptional = c1.inv().sqrt()
+    if exceptional.sgn0():
+        exceptional = -exceptional
+    random.seed(254)
+    us = [exceptional, Fq2(0), Fq2(1), Fq2(0, 1), Fq2(p - 1, 3)]
+    us += [Fq2(random.randrange(p), random.randrange(p)) for _ in range(3)]
+    print("map_to_curve")
+    for u in us:
+        x, y = map_to_curve(u)
+        assert y * y == g(x)
+        print("  u", u, "x", x, "y", y)
+
+    messages = [b"", b"abc", b"abcdef0123456789", b"q128_" + b"q" * 128]
+    messages += [b"a512_" + b"a" * 512]
+    for encoding, h in [("RO", hash_to_curve), ("NU", encode_to_curve)]:
+        dst = b"QUUX-V01-CS02-with-BN254G2_XMD:SHA-256_SVDW_" + encoding.encode() + b"_"
+        print(dst.decode())
+        for msg in messages:
+            P = h(msg, dst)
+            check(P)
+            print("  msg", msg[:16], "x", P[0], "y", P[1])
@@ -4,7 +4,11 @@
 //! This module is temporary, and the extension traits defined here are expected to be
 //! upstreamed into the `ff` and `group` crates after some refactoring.
 
-use crate::CurveExt;
+use crate::{CurveAffine, CurveExt};
+use core::cmp::Ordering;
+use ff::{Field, PrimeField};
+use group::Group;
+use subtle::{Choice, ConstantTimeEq};
 
 pub(crate) struct EndoParameters {
     pub(crate) gamma1: [u64; 4],
@@ -13,8 +17,288 @@ pub(crate) struct EndoParameters {
     pub(crate) b2: [u64; 4],
 }
 
+/// Returns the width-`window` non-adjacent form of the little-endian integer
+/// `bytes`, least significant digit first. Every nonzero digit is odd, lies in
+/// `(-2^(window - 1), 2^(window - 1))` and is followed by at least
+/// `window - 1` zeros.
+///
+/// Panics if `window` is not in `2..=16`.
+pub(crate) fn wnaf_form(bytes: &[u8], window: usize) -> Vec<i64> {
+    assert!((2..=16).contains(&window));
+
+    // One extra limb absorbs the carry of negative digits.
+    let mut limbs: Vec<u64> = bytes
+        .chunks(8)
+        .map(|chunk| {
+            let mut buf = [0u8; 8];
+            buf[..chunk.len()].copy_from_slice(chunk);
+            u64::from_le_bytes(buf)
+        })
+        .chain(Some(0))
+        .collect();
+
+    let width = 1i64 << window;
+    let mut naf = Vec::with_capacity(bytes.len() * 8 + 1);
+    while limbs.iter().any(|&limb| limb != 0) {
+        let mut digit = 0;
+        if limbs[0] & 1 == 1 {
+            digit = (limbs[0] & (width as u64 - 1)) as i64;
+            if digit >= width / 2 {
+                digit -= width;
+            }
+
+            // Subtract the digit so that the next `window - 1` bits are zero.
+            let (mut carry, add) = if digit > 0 {
+                (digit as u64, false)
+            } else {
+                ((-digit) as u64, true)
+            };
+            for limb in limbs.iter_mut() {
+                if carry == 0 {
+                    break;
+                }
+                let (v, overflow) = if add {
+                    limb.overflowing_add(carry)
+                } else {
+                    limb.overflowing_sub(carry)
+                };
+                *limb = v;
+                carry = overflow as u64;
+            }
+        }
+        naf.push(digit);
+
+        for i in 0..limbs.len() {
+            let next = limbs.get(i + 1).map_or(0, |limb| limb << 63);
+            limbs[i] = (limbs[i] >> 1) | next;
+        }
+    }
+    naf
+}
+
+/// Computes `base * scalar` from the width-`window` NAF of `scalar`, with a
+/// table of the `2^(window - 2)` odd multiples of `base`.
+///
+/// This function is not constant time.
+///
+/// Panics if `window` is not in `2..=16`.
+pub fn wnaf_mul<G: Group>(base: &G, scalar: &G::Scalar, window: usize) -> G {
+    let naf = wnaf_form(scalar.to_repr().as_ref(), window);
+    let table = wnaf_table(base, window);
+
+    let mut acc = G::identity();
+    for &digit in naf.iter().rev() {
+        acc = acc.double();
+        wnaf_add(&mut acc, &table, digit);
+    }
+    acc
+}
+
+/// Runs `serial` on `p` and `q`. With the `multicore` feature, large inputs
+/// are split into one chunk per thread, each chunk paying for its own
+/// inversion.
+///
+/// Used by `new_curve_impl!` for `batch_normalize`, so that the feature is
+/// evaluated in this crate rather than in the crate expanding the macro.
+pub fn batch_normalize_with<P: Sync, A: Send>(
+    p: &[P],
+    q: &mut [A],
+    serial: impl Fn(&[P], &mut [A]) + Sync,
+) {
+    assert_eq!(p.len(), q.len());
+
+    #[cfg(feature = "multicore")]
+    if p.len() >= (1 << 12) && rayon::current_num_threads() > 1 {
+        use rayon::prelude::*;
+
+        let chunk = (p.len() + rayon::current_num_threads() - 1) / rayon::current_num_threads();
+        p.par_chunks(chunk)
+            .zip(q.par_chunks_mut(chunk))
+            .for_each(|(p, q)| serial(p, q));
+        return;
+    }
+
+    serial(p, q);
+}
+
+/// Returns the `2^(window - 2)` odd multiples `base, 3 * base, ...` used with
+/// width-`window` NAF digits.
+pub(crate) fn wnaf_table<G: Group>(base: &G, window: usize) -> Vec<G> {
+    let double = base.double();
+    let mut table = Vec::with_capacity(1 << (window - 2));
+    table.push(*base);
+    for i in 1..1 << (window - 2) {
+        table.push(table[i - 1] + double);
+    }
+    table
+}
+
+/// Adds `digit * base` to `acc`, where `table` is the [`wnaf_table`] of `base`.
+pub(crate) fn wnaf_add<G: Group>(acc: &mut G, table: &[G], digit: i64) {
+    match digit.cmp(&0) {
+        Ordering::Greater => *acc += table[(digit / 2) as usize],
+        Ordering::Less => *acc -= table[(-digit / 2) as usize],
+        Ordering::Equal => {}
+    }
+}
+
+/// Curves with an efficient endomorphism `endo(P) = [ZETA] P`, where `ZETA` is
+/// the cube root of unity of the scalar field.
 pub trait CurveEndo: CurveExt {
-    fn decompose_scalar(e: &Self::ScalarExt) -> (u128, bool, u128, bool);
+    /// Decomposes `k` into `(k1, k1_neg, k2, k2_neg)` with `k1` and `k2` of at
+    /// most 128 bits such that `k = ±k1 ∓ ZETA * k2`. `k1` is negated when
+    /// `k1_neg` is set and `ZETA * k2` is added when `k2_neg` is set,
+    /// subtracted otherwise. The signs are computed without branching on `k`.
+    fn decompose_scalar(e: &Self::ScalarExt) -> (u128, Choice, u128, Choice);
+
+    /// Returns the two points `P1` and `P2` such that `self * k = k1 * P1 +
+    /// k2 * P2`, where `k1` and `k2` are the halves of `k`.
+    fn glv_bases(&self, k1_neg: Choice, k2_neg: Choice) -> (Self, Self) {
+        let p1 = Self::conditional_select(self, &-*self, k1_neg);
+        let endo = self.endo();
+        let p2 = Self::conditional_select(&-endo, &endo, k2_neg);
+        (p1, p2)
+    }
+
+    /// Computes `self * k` with the GLV method: `k` is split with
+    /// [`decompose_scalar`](Self::decompose_scalar) and both halves are
+    /// processed in a single 128-bit double-and-add loop, selecting among
+    /// `[O, P1, P2, P1 + P2]` at every step.
+    ///
+    /// The sequence of group operations does not depend on `k`.
+    fn mul_glv(&self, k: &Self::ScalarExt) -> Self {
+        let (k1, k1_neg, k2, k2_neg) = Self::decompose_scalar(k);
+        let (p1, p2) = self.glv_bases(k1_neg, k2_neg);
+        let table = [Self::identity(), p1, p2, p1 + p2];
+
+        let mut acc = Self::identity();
+        for i in (0..128).rev() {
+            acc = acc.double();
+            let idx = (((k1 >> i) & 1) | (((k2 >> i) & 1) << 1)) as u8;
+            let mut term = Self::identity();
+            for (j, point) in table.iter().enumerate() {
+                term.conditional_assign(point, idx.ct_eq(&(j as u8)));
+            }
+            acc += term;
+        }
+        acc
+    }
+
+    /// Computes `self * k` with the GLV method, skipping the leading zero bits
+    /// of the decomposed scalar and the additions of the identity.
+    ///
+    /// This function is not constant time.
+    fn mul_glv_vartime(&self, k: &Self::ScalarExt) -> Self {
+        let (k1, k1_neg, k2, k2_neg) = Self::decompose_scalar(k);
+        let (p1, p2) = self.glv_bases(k1_neg, k2_neg);
+        let table = [p1, p2, p1 + p2];
+
+        let bits = 128 - (k1 | k2).leading_zeros();
+        let mut acc = Self::identity();
+        for i in (0..bits).rev() {
+            acc = acc.double();
+            let idx = ((k1 >> i) & 1) | (((k2 >> i) & 1) << 1);
+            if idx != 0 {
+                acc += table[idx as usize - 1];
+            }
+        }
+        acc
+    }
+}
+
+/// Extension of [`CurveAffine`] with batched affine arithmetic.
+pub trait CurveAffineExt: CurveAffine {
+    /// Returns the affine coordinates of the point. The identity is mapped to
+    /// `(0, 0)`.
+    fn into_coordinates(self) -> (Self::Base, Self::Base);
+
+    /// Builds a point from its affine coordinates without checking that it
+    /// lies on the curve.
+    fn from_coordinates_unchecked(x: Self::Base, y: Self::Base) -> Self;
+
+    /// Computes `acc[i] = acc[i] + rhs[i]` for every `i`. All the slopes of the
+    /// batch are computed with a single field inversion (Montgomery's trick).
+    ///
+    /// This function is not constant time.
+    ///
+    /// Panics if `acc` and `rhs` have different lengths.
+    fn batch_add_assign(acc: &mut [Self], rhs: &[Self]) {
+        // Which formula applies to each pair.
+        #[derive(Clone, Copy)]
+        enum Case {
+            Lhs,
+            Rhs,
+            Identity,
+            Add,
+            Double,
+        }
+
+        assert_eq!(acc.len(), rhs.len());
+
+        let mut cases = Vec::with_capacity(acc.len());
+        let mut denominators = Vec::with_capacity(acc.len());
+        let mut products = Vec::with_capacity(acc.len());
+
+        let mut product = Self::Base::ONE;
+        for (p, q) in acc.iter().zip(rhs.iter()) {
+            let (case, denominator) = if bool::from(q.is_identity()) {
+                (Case::Lhs, Self::Base::ONE)
+            } else if bool::from(p.is_identity()) {
+                (Case::Rhs, Self::Base::ONE)
+            } else {
+                let (x1, y1) = p.into_coordinates();
+                let (x2, y2) = q.into_coordinates();
+                if x1 != x2 {
+                    (Case::Add, x2 - x1)
+                } else if y1 == y2 && !bool::from(y1.is_zero()) {
+                    (Case::Double, y1.double())
+                } else {
+                    (Case::Identity, Self::Base::ONE)
+                }
+            };
+            cases.push(case);
+            denominators.push(denominator);
+            products.push(product);
+            product *= denominator;
+        }
+
+        // All the denominators are nonzero.
+        let mut inv = product.invert().unwrap();
+
+        for (((p, q), case), (denominator, product)) in acc
+            .iter_mut()
+            .zip(rhs.iter())
+            .zip(cases)
+            .zip(denominators.into_iter().zip(products))
+            .rev()
+        {
+            let denominator_inv = inv * product;
+            inv *= denominator;
+
+            let (x1, y1) = p.into_coordinates();
+            let (x2, y2) = q.into_coordinates();
+            let lambda = match case {
+                Case::Lhs => continue,
+                Case::Rhs => {
+                    *p = *q;
+                    continue;
+                }
+                Case::Identity => {
+                    *p = Self::identity();
+                    continue;
+                }
+                Case::Add => (y2 - y1) * denominator_inv,
+                Case::Double => {
+                    let x1_sq = x1.square();
+                    (x1_sq.double() + x1_sq + Self::a()) * denominator_inv
+                }
+            };
+
+            let x3 = lambda.square() - x1 - x2;
+            let y3 = lambda * (x1 - x3) - y1;
+            *p = Self::from_coordinates_unchecked(x3, y3);
+        }
+    }
 }
 
 /// Compute a + b + carry, returning the result and the new carry over.
@@ -70,3 +354,54 @@ pub(crate) fn mul_512(a: [u64; 4], b: [u64; 4]) -> [u64; 8] {
 
     [r0, r1, r2, r3, r4, r5, r6, carry_out]
 }
+
+#[cfg(test)]
+mod test {
+    use super::{wnaf_form, wnaf_mul};
+    use crate::bn256::{Fr, G1};
+    use ff::{Field, PrimeField};
+    use group::Group;
+    use rand_core::OsRng;
+
+    #[test]
+    fn test_wnaf_form() {
+        let scalars = [Fr::ZERO, Fr::ONE, -Fr::ONE, Fr::from(0xdead_beef)]
+            .into_iter()
+            .chain((0..100).map(|_| Fr::random(OsRng)));
+        for scalar in scalars {
+            for window in 2..=16 {
+                let naf = wnaf_form(scalar.to_repr().as_ref(), window);
+
+                let mut nonzero_at = None;
+                let mut acc = Fr::ZERO;
+                for (i, &digit) in naf.iter().enumerate().rev() {
+                    acc = acc.double();
+                    if digit != 0 {
+                        assert_eq!(digit & 1, 1);
+                        assert!(digit.abs() < 1 << (window - 1));
+                        if let Some(j) = nonzero_at {
+                            assert!(j - i >= window);
+                        }
+                        nonzero_at = Some(i);
+                    }
+                    acc +=
+                        Fr::from(digit.unsigned_abs()) * if digit < 0 { -Fr::ONE } else { Fr::ONE };
+                }
+                assert_eq!(acc, scalar);
+            }
+        }
+    }
+
+    #[test]
+    fn test_wnaf_mul() {
+        let base = G1::random(OsRng);
+        for window in 2..=8 {
+            for _ in 0..20 {
+                let scalar = Fr::random(OsRng);
+                assert_eq!(wnaf_mul(&base, &scalar, window), base * scalar);
+            }
+            assert_eq!(wnaf_mul(&base, &Fr::ZERO, window), G1::identity());
+            assert_eq!(wnaf_mul(&G1::identity(), &Fr::ONE, window), G1::identity());
+        }
+    }
+}
@@ -5,12 +5,18 @@ use crate::arithmetic::EndoParameters;
 use crate::bn256::Fq;
 use crate::bn256::Fq2;
 use crate::bn256::Fr;
+use crate::bn256::{BN_X, FROBENIUS_COEFF_FQ6_C1, XI_TO_Q_MINUS_1_OVER_2};
 use crate::endo;
 use crate::ff::WithSmallOrderMulGroup;
 use crate::ff::{Field, PrimeField};
 use crate::group::Curve;
 use crate::group::{cofactor::CofactorGroup, prime::PrimeCurveAffine, Group, GroupEncoding};
-use crate::hash_to_curve::svdw_hash_to_curve;
+use crate::hash_to_curve::{
+    hash_to_field_with, legacy_dst, svdw_encode_to_curve, svdw_encode_to_curve_with_len,
+    svdw_hash_to_curve, svdw_hash_to_curve_with, svdw_map_to_curve, svdw_map_to_curve_inverse,
+    svdw_precomputed_constants, ElligatorSquared, EncodeToCurve, ExpandMessage, ExpandMsgXmd,
+    ExpandMsgXmdBlake2b, HashToCurveWith, Suite,
+};
 use crate::{
     impl_add_binop_specify_output, impl_binops_additive, impl_binops_additive_specify_output,
     impl_binops_multiplicative, impl_binops_multiplicative_mixed, impl_sub_binop_specify_output,
@@ -53,7 +59,11 @@ new_curve_impl!(
     G2_A,
     G2_B,
     "bn256_g2",
-    |_, _| unimplemented!(),
+    |curve_id, domain_prefix| {
+        let dst = legacy_dst(domain_prefix, curve_id, "XMD:BLAKE2b", "SVDW", "RO");
+        let hasher = svdw_hash_to_curve_with::<G2, ExpandMsgXmdBlake2b, 128>(dst, G2::SVDW_Z);
+        Box::new(move |message: &[u8]| hasher(message).clear_cofactor())
+    },
 );
 
 const G1_GENERATOR_X: Fq = Fq::one();
@@ -126,6 +136,7 @@ const ENDO_PARAMS_BN: EndoParameters = EndoParameters {
 };
 
 endo!(G1, Fr, ENDO_PARAMS_BN);
+endo!(G2, Fr, ENDO_PARAMS_BN);
 
 impl group::cofactor::CofactorGroup for G1 {
     type Subgroup = G1;
@@ -146,51 +157,71 @@ impl group::cofactor::CofactorGroup for G1 {
 impl CofactorGroup for G2 {
     type Subgroup = G2;
 
+    /// Maps `Q` to `[x] Q + psi([3x] Q) + psi^2([x] Q) + psi^3(Q)`, which lies
+    /// in the `r`-torsion subgroup for every point of the twist.
+    /// See https://eprint.iacr.org/2011/615.pdf, section 6.1 and
+    /// https://eprint.iacr.org/2017/419.pdf, section 4.1.
     fn clear_cofactor(&self) -> Self {
-        // "0x30644e72e131a029b85045b68181585e06ceecda572a2489345f2299c0f9fa8d"
-        let e: [u8; 32] = [
-            0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81,
-            0x58, 0x5e, 0x06, 0xce, 0xec, 0xda, 0x57, 0x2a, 0x24, 0x89, 0x34, 0x5f, 0x22, 0x99,
-            0xc0, 0xf9, 0xfa, 0x8d,
-        ];
-
-        // self * COFACTOR_G2
-        let mut acc = G2::identity();
-        for bit in e
-            .iter()
-            .flat_map(|byte| (0..8).rev().map(move |i| Choice::from((byte >> i) & 1u8)))
-            .skip(1)
-        {
-            acc = acc.double();
-            acc = G2::conditional_select(&acc, &(acc + self), bit);
-        }
-        acc
+        let x_q = self.mul_by_x();
+        let x3_q = x_q.double() + x_q;
+        x_q + x3_q.psi() + x_q.psi().psi() + self.psi().psi().psi()
     }
 
     fn into_subgroup(self) -> CtOption<Self::Subgroup> {
-        unimplemented!();
+        CtOption::new(self, self.is_torsion_free())
     }
 
+    /// Checks `psi(P) == [6x^2] P`, which holds exactly for the points of the
+    /// `r`-torsion subgroup on the BN twist.
+    /// See https://eprint.iacr.org/2022/352.pdf, section 4.
     fn is_torsion_free(&self) -> Choice {
-        // "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001"
-        let e: [u8; 32] = [
-            0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81,
-            0x58, 0x5d, 0x28, 0x33, 0xe8, 0x48, 0x79, 0xb9, 0x70, 0x91, 0x43, 0xe1, 0xf5, 0x93,
-            0xf0, 0x00, 0x00, 0x01,
-        ];
+        self.psi().ct_eq(&self.mul_by_six_x_square())
+    }
+}
+
+impl G2 {
+    /// `6x^2`, the eigenvalue of `psi` on the `r`-torsion subgroup, where `x`
+    /// is the BN parameter.
+    const SIX_X_SQUARE: u128 = 6 * (BN_X as u128) * (BN_X as u128);
+
+    /// Applies the endomorphism `psi = untwist^-1 . frobenius . untwist` of the
+    /// twist: `psi(x, y) = (conj(x) * xi^((p - 1) / 3), conj(y) * xi^((p - 1) / 2))`.
+    pub fn psi(&self) -> Self {
+        let mut x = self.x;
+        let mut y = self.y;
+        let mut z = self.z;
+        x.conjugate();
+        y.conjugate();
+        z.conjugate();
+        G2 {
+            x: x * FROBENIUS_COEFF_FQ6_C1[1],
+            y: y * XI_TO_Q_MINUS_1_OVER_2,
+            z,
+        }
+    }
 
-        // self * GROUP_ORDER;
+    /// Computes `[6x^2] self`.
+    fn mul_by_six_x_square(&self) -> Self {
+        self.mul_by_public_u128(Self::SIX_X_SQUARE)
+    }
+
+    /// Computes `[x] self`, where `x` is the BN parameter.
+    fn mul_by_x(&self) -> Self {
+        self.mul_by_public_u128(BN_X as u128)
+    }
 
+    /// Computes `[k] self` for a public constant `k`: the sequence of group
+    /// operations only depends on `k`.
+    fn mul_by_public_u128(&self, k: u128) -> Self {
+        let bits = 128 - k.leading_zeros();
         let mut acc = G2::identity();
-        for bit in e
-            .iter()
-            .flat_map(|byte| (0..8).rev().map(move |i| Choice::from((byte >> i) & 1u8)))
-            .skip(1)
-        {
+        for i in (0..bits).rev() {
             acc = acc.double();
-            acc = G2::conditional_select(&acc, &(acc + self), bit);
+            if (k >> i) & 1 == 1 {
+                acc += self;
+            }
         }
-        acc.is_identity()
+        acc
     }
 }
 
@@ -198,18 +229,192 @@ impl G1 {
     const SVDW_Z: Fq = Fq::ONE;
 }
 
+impl G2 {
+    const SVDW_Z: Fq2 = Fq2::ONE;
+}
+
+impl EncodeToCurve for G1 {
+    fn encode_to_curve<'a>(domain_prefix: &'a str) -> Box<dyn Fn(&[u8]) -> Self + 'a> {
+        svdw_encode_to_curve(Self::CURVE_ID, domain_prefix, G1::SVDW_Z)
+    }
+}
+
+impl HashToCurveWith for G1 {
+    fn hash_to_curve_with<'a, X: ExpandMessage + 'a>(
+        dst: &[u8],
+    ) -> Box<dyn Fn(&[u8]) -> Self + 'a> {
+        svdw_hash_to_curve_with::<G1, X, 64>(dst.to_vec(), G1::SVDW_Z)
+    }
+}
+
+impl EncodeToCurve for G2 {
+    fn encode_to_curve<'a>(domain_prefix: &'a str) -> Box<dyn Fn(&[u8]) -> Self + 'a> {
+        let encoder =
+            svdw_encode_to_curve_with_len::<G2, 128>(Self::CURVE_ID, domain_prefix, G2::SVDW_Z);
+        Box::new(move |message| encoder(message).clear_cofactor())
+    }
+}
+
+impl HashToCurveWith for G2 {
+    fn hash_to_curve_with<'a, X: ExpandMessage + 'a>(
+        dst: &[u8],
+    ) -> Box<dyn Fn(&[u8]) -> Self + 'a> {
+        let hasher = svdw_hash_to_curve_with::<G2, X, 128>(dst.to_vec(), G2::SVDW_Z);
+        Box::new(move |message| hasher(message).clear_cofactor())
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref G1_SVDW_CONSTANTS: [Fq; 4] = svdw_precomputed_constants::<G1>(G1::SVDW_Z);
+    static ref G2_SVDW_CONSTANTS: [Fq2; 4] = svdw_precomputed_constants::<G2>(G2::SVDW_Z);
+}
+
+/// The `BN254G1_XMD:SHA-256_SVDW_RO_` and `BN254G1_XMD:SHA-256_SVDW_NU_`
+/// hash-to-curve suites, compatible with gnark-crypto.
+#[derive(Clone, Copy, Debug)]
+pub struct Bn254G1XmdSha256Svdw;
+
+impl Suite for Bn254G1XmdSha256Svdw {
+    type Curve = G1;
+    type Expander = ExpandMsgXmd<sha2::Sha256>;
+    const RO_ID: &'static str = "BN254G1_XMD:SHA-256_SVDW_RO_";
+    const NU_ID: &'static str = "BN254G1_XMD:SHA-256_SVDW_NU_";
+
+    fn map_to_curve(u: Fq) -> CtOption<G1> {
+        let [c1, c2, c3, c4] = *G1_SVDW_CONSTANTS;
+        svdw_map_to_curve(u, c1, c2, c3, c4, G1::SVDW_Z)
+    }
+}
+
+/// The `BN254G2_XMD:SHA-256_SVDW_RO_` and `BN254G2_XMD:SHA-256_SVDW_NU_`
+/// hash-to-curve suites, compatible with gnark-crypto.
+#[derive(Clone, Copy, Debug)]
+pub struct Bn254G2XmdSha256Svdw;
+
+impl Suite for Bn254G2XmdSha256Svdw {
+    type Curve = G2;
+    type Expander = ExpandMsgXmd<sha2::Sha256>;
+    const RO_ID: &'static str = "BN254G2_XMD:SHA-256_SVDW_RO_";
+    const NU_ID: &'static str = "BN254G2_XMD:SHA-256_SVDW_NU_";
+
+    /// Hashes to `2 * count` elements of `Fq`, the coordinates of the `count`
+    /// elements of `Fq2`.
+    fn hash_to_field(message: &[u8], dst: &[u8], count: usize) -> Vec<Fq2> {
+        hash_to_field_with::<Fq, Self::Expander>(dst, message, 2 * count)
+            .chunks(2)
+            .map(|c| Fq2::new(c[0], c[1]))
+            .collect()
+    }
+
+    fn map_to_curve(u: Fq2) -> CtOption<G2> {
+        let [c1, c2, c3, c4] = *G2_SVDW_CONSTANTS;
+        svdw_map_to_curve(u, c1, c2, c3, c4, G2::SVDW_Z)
+    }
+
+    fn clear_cofactor(p: G2) -> G2 {
+        p.clear_cofactor()
+    }
+}
+
+impl ElligatorSquared for G1 {
+    fn map_to_curve(u: Fq) -> CtOption<G1> {
+        let [c1, c2, c3, c4] = *G1_SVDW_CONSTANTS;
+        svdw_map_to_curve(u, c1, c2, c3, c4, G1::SVDW_Z)
+    }
+
+    fn map_to_curve_inverse(&self) -> [CtOption<Fq>; 8] {
+        let [c1, c2, c3, c4] = *G1_SVDW_CONSTANTS;
+        svdw_map_to_curve_inverse(self, c1, c2, c3, c4, G1::SVDW_Z)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::arithmetic::CurveEndo;
-    use crate::bn256::{Fr, G1, G2};
-    use crate::CurveExt;
+    use crate::bn256::{Bn254G1XmdSha256Svdw, Fq, Fq2, Fr, G1Affine, G2Affine, G1, G2};
+    use crate::hash_to_curve::{
+        svdw_map_to_curve, svdw_precomputed_constants, EncodeToCurve, Suite,
+    };
+    use crate::tests::fe_from_str;
+    use crate::{CurveAffine, CurveExt};
     use ff::Field;
     use ff::{PrimeField, WithSmallOrderMulGroup};
+    use group::{prime::PrimeCurveAffine, Curve, Group};
     use rand_core::OsRng;
 
     #[test]
     fn test_hash_to_curve() {
         crate::tests::curve::hash_to_curve_test::<G1>();
+        crate::tests::curve::hash_to_curve_with_test::<G1>("SVDW");
+    }
+
+    #[test]
+    fn test_encode_to_curve() {
+        crate::tests::curve::encode_to_curve_test::<G1>();
+    }
+
+    #[test]
+    fn test_g2_encode_to_curve() {
+        use group::cofactor::CofactorGroup;
+
+        crate::tests::curve::encode_to_curve_test::<G2>();
+        let p = G2::encode_to_curve("test")(b"abc");
+        assert!(bool::from(p.is_torsion_free()));
+        assert!(is_torsion_free_slow(&p));
+    }
+
+    #[test]
+    fn test_encode_to_curve_suite() {
+        // `encodeToG1Vector` of https://github.com/ConsenSys/gnark-crypto/blob/441dc0ffe639294b8d09e394f24ba7575577229c/ecc/bn254/hash_vectors_test.go#L4-L28,
+        // the same points as in `test_map_to_curve`: the cofactor of G1 is one.
+        let dst = format!("QUUX-V01-CS02-with-{}", Bn254G1XmdSha256Svdw::NU_ID);
+        for (message, (x, y)) in [
+            (
+                &b""[..],
+                (
+                    "0x1bb8810e2ceaf04786d4efd216fc2820ddd9363712efc736ada11049d8af5925",
+                    "0x1efbf8d54c60d865cce08437668ea30f5bf90d287dbd9b5af31da852915e8f11",
+                ),
+            ),
+            (
+                b"abc",
+                (
+                    "0xda4a96147df1f35b0f820bd35c6fac3b80e8e320de7c536b1e054667b22c332",
+                    "0x189bd3fbffe4c8740d6543754d95c790e44cd2d162858e3b733d2b8387983bb7",
+                ),
+            ),
+            (
+                b"abcdef0123456789",
+                (
+                    "0x2ff727cfaaadb3acab713fa22d91f5fddab3ed77948f3ef6233d7ea9b03f4da1",
+                    "0x304080768fd2f87a852155b727f97db84b191e41970506f0326ed4046d1141aa",
+                ),
+            ),
+        ] {
+            let expected = G1Affine::from_xy(fe_from_str(x), fe_from_str(y)).unwrap();
+            let output = Bn254G1XmdSha256Svdw::encode_to_curve(message, dst.as_bytes());
+            assert_eq!(output.to_affine(), expected);
+        }
+    }
+
+    #[test]
+    fn test_elligator_squared() {
+        crate::tests::curve::elligator_squared_test::<G1>();
+    }
+
+    #[test]
+    fn test_map_to_curve_exceptional() {
+        // For `u = 0` and `u = 1 / 2`, `(1 - c1 * u^2) * (1 + c1 * u^2)` is
+        // zero for `c1 = 4`, and both map to `x = c2`.
+        let expected = G1Affine::from_xy(
+            fe_from_str("0x183227397098d014dc2822db40c0ac2ecbc0b548b438e5469e10460b6c3e7ea3"),
+            fe_from_str("0x0a6ea289876b139cfe2cd1f08c065a2ab4aad542eaccb013520ea36934e877b4"),
+        )
+        .unwrap();
+        for u in [Fq::ZERO, Fq::TWO_INV] {
+            let output = Bn254G1XmdSha256Svdw::map_to_curve(u).unwrap();
+            assert_eq!(output.to_affine(), expected);
+        }
     }
 
     #[test]
@@ -264,6 +469,82 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_hash_to_curve_xof() {
+        use crate::hash_to_curve::{ExpandMessage, ExpandMsgXof, HashToCurveWith};
+        use ff::FromUniformBytes;
+        use sha3::Shake256;
+
+        // Built from pieces with published vectors: the SHAKE256 expander of
+        // `test_expand_message_xof_shake256` and the map of `test_map_to_curve`.
+        let dst = b"QUUX-V01-CS02-with-BN254G1_XOF:SHAKE256_SVDW_RO_";
+        let hasher = G1::hash_to_curve_with::<ExpandMsgXof<Shake256, 256>>(dst);
+        let q128 = [&b"q128_"[..], &[b'q'; 128]].concat();
+        let a512 = [&b"a512_"[..], &[b'a'; 512]].concat();
+        for message in [&b""[..], b"abc", b"abcdef0123456789", &q128, &a512] {
+            let uniform_bytes = ExpandMsgXof::<Shake256, 256>::expand_message(message, dst, 128);
+            let expected: G1 = uniform_bytes
+                .chunks(64)
+                .map(|chunk| {
+                    let mut little: [u8; 64] = chunk.try_into().unwrap();
+                    little.reverse();
+                    let u = Fq::from_uniform_bytes(&little);
+                    Bn254G1XmdSha256Svdw::map_to_curve(u).unwrap()
+                })
+                .sum();
+            assert_eq!(hasher(message), expected);
+        }
+    }
+
+    #[test]
+    fn test_hash_to_curve_suite() {
+        // `hashToG1Vector` of https://github.com/ConsenSys/gnark-crypto/blob/441dc0ffe639294b8d09e394f24ba7575577229c/ecc/bn254/hash_vectors_test.go
+        let dst = format!("QUUX-V01-CS02-with-{}", Bn254G1XmdSha256Svdw::RO_ID);
+        let q128 = [&b"q128_"[..], &[b'q'; 128]].concat();
+        let a512 = [&b"a512_"[..], &[b'a'; 512]].concat();
+        for (message, (x, y)) in [
+            (
+                &b""[..],
+                (
+                    "0xa976ab906170db1f9638d376514dbf8c42aef256a54bbd48521f20749e59e86",
+                    "0x2925ead66b9e68bfc309b014398640ab55f6619ab59bc1fab2210ad4c4d53d5",
+                ),
+            ),
+            (
+                b"abc",
+                (
+                    "0x23f717bee89b1003957139f193e6be7da1df5f1374b26a4643b0378b5baf53d1",
+                    "0x4142f826b71ee574452dbc47e05bc3e1a647478403a7ba38b7b93948f4e151d",
+                ),
+            ),
+            (
+                b"abcdef0123456789",
+                (
+                    "0x187dbf1c3c89aceceef254d6548d7163fdfa43084145f92c4c91c85c21442d4a",
+                    "0xabd99d5b0000910b56058f9cc3b0ab0a22d47cf27615f588924fac1e5c63b4d",
+                ),
+            ),
+            (
+                &q128,
+                (
+                    "0xfe2b0743575324fc452d590d217390ad48e5a16cf051bee5c40a2eba233f5c",
+                    "0x794211e0cc72d3cbbdf8e4e5cd6e7d7e78d101ff94862caae8acbe63e9fdc78",
+                ),
+            ),
+            (
+                &a512,
+                (
+                    "0x1b05dc540bd79fd0fea4fbb07de08e94fc2e7bd171fe025c479dc212a2173ce",
+                    "0x1bf028afc00c0f843d113758968f580640541728cfc6d32ced9779aa613cd9b0",
+                ),
+            ),
+        ] {
+            let expected = G1Affine::from_xy(fe_from_str(x), fe_from_str(y)).unwrap();
+            let output = Bn254G1XmdSha256Svdw::hash_to_curve(message, dst.as_bytes());
+            assert_eq!(output.to_affine(), expected);
+        }
+    }
+
     #[test]
     fn test_curve() {
         crate::tests::curve::curve_tests::<G1>();
@@ -289,6 +570,7 @@ mod tests {
         for _ in 0..100000 {
             let k = Fr::random(OsRng);
             let (k1, k1_neg, k2, k2_neg) = G1::decompose_scalar(&k);
+            let (k1_neg, k2_neg) = (bool::from(k1_neg), bool::from(k2_neg));
             if k1_neg & k2_neg {
                 assert_eq!(k, -Fr::from_u128(k1) + Fr::ZETA * Fr::from_u128(k2))
             } else if k1_neg {
@@ -299,6 +581,500 @@ mod tests {
                 assert_eq!(k, Fr::from_u128(k1) - Fr::ZETA * Fr::from_u128(k2))
             }
         }
+
+        crate::tests::curve::endo_decomposition_test::<G1>();
+        crate::tests::curve::endo_decomposition_test::<G2>();
+    }
+
+    #[test]
+    fn test_mul_vartime() {
+        let p1 = G1::random(OsRng);
+        let p2 = G2::random(OsRng);
+        for _ in 0..100 {
+            let s = Fr::random(OsRng);
+            assert_eq!(p1.mul_vartime(&s), p1 * s);
+            assert_eq!(p2.mul_vartime(&s), p2 * s);
+            for window in 2..=6 {
+                assert_eq!(p1.mul_wnaf(&s, window), p1 * s);
+            }
+        }
+        assert!(bool::from(p1.mul_vartime(&Fr::ZERO).is_identity()));
+        assert_eq!(p1.mul_vartime(&-Fr::ONE), -p1);
+    }
+
+    #[test]
+    fn test_mul_generator() {
+        for _ in 0..100 {
+            let s = Fr::random(OsRng);
+            assert_eq!(G1::mul_generator(&s), G1::generator() * s);
+            assert_eq!(G2::mul_generator(&s), G2::generator() * s);
+        }
+        assert_eq!(G1::mul_generator(&Fr::ONE), G1::generator());
+    }
+
+    /// Computes `[r] P`, the multiplication by the group order.
+    fn mul_by_order(p: &G2) -> G2 {
+        // "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001"
+        let e: [u8; 32] = [
+            0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81,
+            0x58, 0x5d, 0x28, 0x33, 0xe8, 0x48, 0x79, 0xb9, 0x70, 0x91, 0x43, 0xe1, 0xf5, 0x93,
+            0xf0, 0x00, 0x00, 0x01,
+        ];
+        let mut acc = G2::identity();
+        for bit in e
+            .iter()
+            .flat_map(|byte| (0..8).rev().map(move |i| (byte >> i) & 1u8))
+        {
+            acc = acc.double();
+            if bit == 1 {
+                acc += p;
+            }
+        }
+        acc
+    }
+
+    fn is_torsion_free_slow(p: &G2) -> bool {
+        bool::from(mul_by_order(p).is_identity())
+    }
+
+    /// Returns a random point of the twist, which is almost never in the
+    /// `r`-torsion subgroup.
+    fn random_twist_point() -> G2 {
+        loop {
+            let x = Fq2::random(OsRng);
+            if let Some(y) = Option::<Fq2>::from(G2Affine::y2(x).sqrt()) {
+                return G2Affine { x, y }.to_curve();
+            }
+        }
+    }
+
+    #[test]
+    fn test_g2_psi() {
+        let p = G2::random(OsRng);
+        assert_eq!(p.psi(), p * Fr::from_u128(G2::SIX_X_SQUARE));
+        assert_eq!(p.psi().psi().psi().psi(), p.psi().psi() - p);
+        assert!(bool::from(G2::identity().psi().is_identity()));
+    }
+
+    #[test]
+    fn test_g2_subgroup() {
+        use group::cofactor::CofactorGroup;
+
+        assert!(bool::from(G2::identity().is_torsion_free()));
+        assert!(bool::from(G2::generator().is_torsion_free()));
+
+        for _ in 0..20 {
+            let p = G2::random(OsRng);
+            assert!(is_torsion_free_slow(&p));
+            assert!(bool::from(p.is_torsion_free()));
+            assert_eq!(p.into_subgroup().unwrap(), p);
+
+            let q = random_twist_point();
+            assert!(!is_torsion_free_slow(&q));
+            assert!(!bool::from(q.is_torsion_free()));
+            assert!(bool::from(q.into_subgroup().is_none()));
+
+            // A subgroup point shifted by a point of order dividing the
+            // cofactor is rejected.
+            let q = mul_by_order(&q) + p;
+            assert!(!bool::from(q.is_identity()));
+            assert_eq!(bool::from(q.is_torsion_free()), is_torsion_free_slow(&q));
+            assert!(!bool::from(q.is_torsion_free()));
+        }
+    }
+
+    /// Computes `[h] P`, the multiplication by the cofactor of G2.
+    fn clear_cofactor_slow(p: &G2) -> G2 {
+        // "0x30644e72e131a029b85045b68181585e06ceecda572a2489345f2299c0f9fa8d"
+        let e: [u8; 32] = [
+            0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81,
+            0x58, 0x5e, 0x06, 0xce, 0xec, 0xda, 0x57, 0x2a, 0x24, 0x89, 0x34, 0x5f, 0x22, 0x99,
+            0xc0, 0xf9, 0xfa, 0x8d,
+        ];
+        let mut acc = G2::identity();
+        for bit in e
+            .iter()
+            .flat_map(|byte| (0..8).rev().map(move |i| (byte >> i) & 1u8))
+        {
+            acc = acc.double();
+            if bit == 1 {
+                acc += p;
+            }
+        }
+        acc
+    }
+
+    #[test]
+    fn test_g2_clear_cofactor() {
+        use group::cofactor::CofactorGroup;
+
+        // On the twist, `clear_cofactor` is `[m] . [h]^-1` times the multiplication
+        // by the cofactor `h`, with `m = x + 3xp + xp^2 + p^3 mod r`.
+        // "0x30644e72e131a0295e6dd9e7e0acccb0c28f069fbb966e3f8236b51f1ef338ef"
+        let m_over_h = Fr::from_raw([
+            0x8236b51f1ef338ef,
+            0xc28f069fbb966e3f,
+            0x5e6dd9e7e0acccb0,
+            0x30644e72e131a029,
+        ]);
+
+        assert!(bool::from(G2::identity().clear_cofactor().is_identity()));
+        for _ in 0..20 {
+            let q = random_twist_point();
+            let cleared = q.clear_cofactor();
+            assert!(!bool::from(cleared.is_identity()));
+            assert!(is_torsion_free_slow(&cleared));
+            assert_eq!(cleared, clear_cofactor_slow(&q) * m_over_h);
+
+            let p = G2::random(OsRng);
+            assert_eq!(p.clear_cofactor(), clear_cofactor_slow(&p) * m_over_h);
+        }
+    }
+
+    #[test]
+    fn test_g2_hash_to_curve() {
+        use group::cofactor::CofactorGroup;
+
+        crate::tests::curve::hash_to_curve_test::<G2>();
+        crate::tests::curve::hash_to_curve_with_test::<G2>("SVDW");
+
+        let hasher = G2::hash_to_curve("test");
+        for message in [&b""[..], b"abc", &[0xff; 200]] {
+            let p = hasher(message);
+            assert_eq!(p, hasher(message));
+            assert!(!bool::from(p.is_identity()));
+            assert!(bool::from(p.is_torsion_free()));
+            assert!(is_torsion_free_slow(&p));
+        }
+        assert_ne!(hasher(b"abc"), G2::hash_to_curve("other")(b"abc"));
+    }
+
+    #[test]
+    fn test_g2_hash_to_curve_suite() {
+        use crate::bn256::Bn254G2XmdSha256Svdw;
+
+        // Generated by `python3 script/bn254_g2_hash_vectors.py`, which
+        // implements the suites as gnark-crypto's bn254 `HashToG2` and
+        // `EncodeToG2` do, without any code or constant of this crate: its
+        // cofactor clearing is `[x]Q + psi([3x]Q) + psi^2([x]Q) + psi^3(Q)`
+        // evaluated on affine points.
+        let q128 = [&b"q128_"[..], &[b'q'; 128]].concat();
+        let a512 = [&b"a512_"[..], &[b'a'; 512]].concat();
+
+        let dst = format!("QUUX-V01-CS02-with-{}", Bn254G2XmdSha256Svdw::RO_ID);
+        for (message, ((x0, x1), (y0, y1))) in [
+            (
+                &b""[..],
+                (
+                    (
+                        "0x1192005a0f121921a6d5629946199e4b27ff8ee4d6dd4f9581dc550ade851300",
+                        "0x1747d950a6f23c16156e2171bce95d1189b04148ad12628869ed21c96a8c9335",
+                    ),
+                    (
+                        "0x498f6bb5ac309a07d9a8b88e6ff4b8de0d5f27a075830e1eb0e68ea318201d8",
+                        "0x2c9755350ca363ef2cf541005437221c5740086c2e909b71d075152484e845f4",
+                    ),
+                ),
+            ),
+            (
+                b"abc",
+                (
+                    (
+                        "0x16c88b54eec9af86a41569608cd0f60aab43464e52ce7e6e298bf584b94fccd2",
+                        "0xb5db3ca7e8ef5edf3a33dfc3242357fbccead98099c3eb564b3d9d13cba4efd",
+                    ),
+                    (
+                        "0x1c42ba524cb74db8e2c680449746c028f7bea923f245e69f89256af2d6c5f3ac",
+                        "0x22d02d2da7f288545ff8789e789902245ab08c6b1d253561eec789ec2c1bd630",
+                    ),
+                ),
+            ),
+            (
+                b"abcdef0123456789",
+                (
+                    (
+                        "0x1435fd84aa43c699230e371f6fea3545ce7e053cbbb06a320296a2b81efddc70",
+                        "0x2a8a360585b6b05996ef69c3c09b2c6fb17afe2b1e944f07559c53178eabf171",
+                    ),
+                    (
+                        "0x2820188dcdc13ffdca31694942418afa1d6dfaaf259d012fab4da52b0f592e38",
+                        "0x142f08e2441ec431defc24621b73cfe0252d19b243cb55b84bdeb85de039207a",
+                    ),
+                ),
+            ),
+            (
+                &q128,
+                (
+                    (
+                        "0x2cffc213fb63d00d923cb22cda5a2904837bb93a2fe6e875c532c51744388341",
+                        "0x2718ef38d1bc4347f0266c774c8ef4ee5fa7056cc27a4bd7ecf7a888efb95b26",
+                    ),
+                    (
+                        "0x232553f728341afa64ce66d00535764557a052e38657594e10074ad28728c584",
+                        "0x2206ec0a9288f31ed78531c37295df3b56c42a1284443ee9893adb1521779001",
+                    ),
+                ),
+            ),
+            (
+                &a512,
+                (
+                    (
+                        "0x242a0a159f36f87065e7c5170426012087023165ce47a486e53d6e2845ca625a",
+                        "0x17f9f6292998cf18ccc155903c1fe6b6465d40c794a3e1ed644a4182ad639f4a",
+                    ),
+                    (
+                        "0x2dc5b7b65c9c79e6ef4afab8fbe3083c66d4ce31c78f6621ece17ecc892cf4b3",
+                        "0x18ef4886c818f01fdf309bc9a46dd904273917f85e74ecd0de62460a68122037",
+                    ),
+                ),
+            ),
+        ] {
+            let output = Bn254G2XmdSha256Svdw::hash_to_curve(message, dst.as_bytes());
+            assert_eq!(
+                output.to_affine(),
+                G2Affine::from_xy(fq2(x0, x1), fq2(y0, y1)).unwrap()
+            );
+        }
+
+        let dst = format!("QUUX-V01-CS02-with-{}", Bn254G2XmdSha256Svdw::NU_ID);
+        for (message, ((x0, x1), (y0, y1))) in [
+            (
+                &b""[..],
+                (
+                    (
+                        "0x4e9ea7f5807198397a99e234e91d4b9e6cadf0135ebedd97fd75cffed6e994d",
+                        "0x70077acfda8443392fb30222ba96b63f4b734e678494bf4ed0e07074b440a7b",
+                    ),
+                    (
+                        "0x2d3653bf41ec170ce2d48774d02393c8d5f60fee5690b4f8cbc8531e269227f9",
+                        "0xa7cf5d0d356f0c4d163570209e5f8f749bf91dc2a7d9ba58199a95ce02242b4",
+                    ),
+                ),
+            ),
+            (
+                b"abc",
+                (
+                    (
+                        "0x101e2f3d9fa22cb435ecb67d5284dc27c247856d6de4e420e1812e0bcea5afd8",
+                        "0x29226a3ca7415a541599274bf9e805050c82d443fd953481b17236325be3b6b7",
+                    ),
+                    (
+                        "0x290bf12841dd276211effe86af369c11a2cb364c443981d0faf347cfb7b68715",
+                        "0x2e7c8a61fe36735852597ac564966560afe0ef8221918d5534e57f3096f7047d",
+                    ),
+                ),
+            ),
+            (
+                b"abcdef0123456789",
+                (
+                    (
+                        "0xfcda542dd52f0e527bf828e63fe2a1f63a05c9a5c7a28865cfef247c6e1e8a6",
+                        "0x2d0bb492bb59847c106af8285fae5be0b5f96b6dcad56b3a0c7ddc364ae55a3a",
+                    ),
+                    (
+                        "0x172d50b483e9bb9aa230e7cb82fbd522af1b73c1643bbd022614533311071780",
+                        "0xafb68b6e28f44f49d6ab4c3014e73f7e07fd4d0b13a9519b798e9f1927a47b9",
+                    ),
+                ),
+            ),
+            (
+                &q128,
+                (
+                    (
+                        "0x1d050758368c65df07014cab4752d8244ddf21691ab6418a3493bcc2a946b38d",
+                        "0x2596aa6bcb29439a9cdc7cfe0b9d247a890a4295dc17d053c293c7e40c27387f",
+                    ),
+                    (
+                        "0x2f84eec5eaa87952d0d81c93c3f470c1e1a00d0ba307d8fda78b76841aca8e82",
+                        "0x27aef639d6eb4157c6f076e9fdae2f9eb15042dea92304fc54ebd5f69c5c3443",
+                    ),
+                ),
+            ),
+            (
+                &a512,
+                (
+                    (
+                        "0x13729abbd4fbe2a13bc742960afa9053a4e6be06ea712b0d18153a9ec3854a7",
+                        "0x261e8ebaff3438064599465bb52880e8e8a663b27cfb6d794d90ac60437819a9",
+                    ),
+                    (
+                        "0x132285a30dc36cc14da2d145390a6328e574155ebaece32856fb890d1f7ba16e",
+                        "0x6bd9197b3c0c1cc4d17695042dcbaf0168329a113d358c3b17885f71a394986",
+                    ),
+                ),
+            ),
+        ] {
+            let output = Bn254G2XmdSha256Svdw::encode_to_curve(message, dst.as_bytes());
+            assert_eq!(
+                output.to_affine(),
+                G2Affine::from_xy(fq2(x0, x1), fq2(y0, y1)).unwrap()
+            );
+        }
+    }
+
+    fn fq2(c0: &str, c1: &str) -> Fq2 {
+        Fq2::new(fe_from_str(c0), fe_from_str(c1))
+    }
+
+    #[test]
+    fn test_g2_map_to_curve() {
+        // Constants and outputs of the straightforward SvdW map of
+        // https://datatracker.ietf.org/doc/html/rfc9380#section-6.6.1 with `Z = 1`,
+        // as printed by `python3 script/bn254_g2_hash_vectors.py`.
+        let [c1, c2, c3, c4] = svdw_precomputed_constants::<G2>(G2::SVDW_Z);
+        assert_eq!(
+            [c1, c2, c3, c4],
+            [
+                fq2(
+                    "0x2b149d40ceb8aaae81be18991be06ac3b5b4c5e559dbefa33267e6dc24a138e6",
+                    "0x9713b03af0fed4cd2cafadeed8fdf4a74fa084e52d1852e4a2bd0685c315d2",
+                ),
+                fq2(
+                    "0x183227397098d014dc2822db40c0ac2ecbc0b548b438e5469e10460b6c3e7ea3",
+                    "0x0",
+                ),
+                fq2(
+                    "0x29fd332ab7260112b801fa95b21af64e2e6da55f90a3e510fcbe57377b5ca1ec",
+                    "0x303d1eff1426764bf8408aee24ba0b865e76f77b1267a846b1e9154d01565034",
+                ),
+                fq2(
+                    "0x17365bbe63b1d2078632fe0eb2ac5a41b4e6a9c08b98676721010b008d4eaf99",
+                    "0xf57ffe5fc79e19cd689d7aa4209cad8fe164d7f4694786b388732a995d03755",
+                ),
+            ]
+        );
+        assert!(!bool::from(c3.is_odd()));
+
+        let test_vector = [
+            // `1 - c1 * u^2 = 0`, where `inv0` returns zero and `x = Z`.
+            (
+                fq2(
+                    "0xe6be1329360814a7ffcb1c2f50aa503882f748a8759ab759a9c94905bae3d0a",
+                    "0x10009fb7bc95062257a7d35f6c8332495802a82e755652d245b0f4cbad3d9aa5",
+                ),
+                (
+                    fq2("0x1", "0x0"),
+                    fq2(
+                        "0x7fb3d558dafafb6bf6dd326a5fefe0beca3f9ac3bd999a390d504fad34b0b8c",
+                        "0x2351dcdda257b62181cbd745dfee16d5fdf4eb185bbcf33c20a0fe6eaa9cb4a3",
+                    ),
+                ),
+            ),
+            (
+                fq2("0x0", "0x0"),
+                (
+                    fq2(
+                        "0x17365bbe63b1d2078632fe0eb2ac5a41b4e6a9c08b98676721010b008d4eaf9a",
+                        "0xf57ffe5fc79e19cd689d7aa4209cad8fe164d7f4694786b388732a995d03755",
+                    ),
+                    fq2(
+                        "0x2bc9fff87a744078fa8071076817d3e91050735ed50fd58cec28ab894ffd3258",
+                        "0x1729bddff301ffe7b1e30afc6452c39c91ee831ac9e71bc914d1e4f45f5f66c3",
+                    ),
+                ),
+            ),
+            (
+                fq2("0x1", "0x0"),
+                (
+                    fq2(
+                        "0x1e88195dd9def5f6e93c9a6dc0aa141bd5ae7a5371c1e8af5d4c929f66fd5f72",
+                        "0xd97e43a0a1906b80bd64ff787690430602c21d2f1cc46f961caab7e2517754a",
+                    ),
+                    fq2(
+                        "0x1b4ad98341108cd061c06b5bb0db6b22605fc0e52da6160e6937b01b8b64e935",
+                        "0x59828015df446cf13c7fad97c9944f1f07d8146d1734dd2fd965337aef8af2c",
+                    ),
+                ),
+            ),
+            (
+                fq2("0x0", "0x1"),
+                (
+                    fq2(
+                        "0x2aeb837ddb7962c3af36d8d1720e7b837711c92221173172bb379565ae3c4e12",
+                        "0x2d5d7be747cdb0e734dab9d3b2bbbe964c74ba33cb316909c01743c3a6527954",
+                    ),
+                    fq2(
+                        "0x1efcb2e500d25ecc9115d449a79dbe5668d392604cd6fe1354d18a942e133fc9",
+                        "0x265a3f60991ae5bdb4bec25c860fb928142da63c6dfdde58337a2279a378181f",
+                    ),
+                ),
+            ),
+            (
+                fq2(
+                    "0x30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd46",
+                    "0x3",
+                ),
+                (
+                    fq2(
+                        "0x2dc43fb55d1298bed50280434bf655cc336364117db09b7b1860900586130f81",
+                        "0x16ee767fb7a032be1e63b1d66deac9e3df19ee909f289ad9fe0b8004a39faecf",
+                    ),
+                    fq2(
+                        "0xea3eb3f35eaed2ff7d981d45947dff592fdf8e77038b9bbad4ed5b9e18d9338",
+                        "0xbe7b44fdb9827d0014c6906f321f59e50e51e61b4eb192e52c379ae0eb8cefc",
+                    ),
+                ),
+            ),
+            (
+                fq2(
+                    "0x3a133837a3d9895706bfa5a978c7a868fad047403e9a998040c0a15ff32385",
+                    "0x2b4107667beafe22bb8a0b59530f632cf9e0b742b53f787a80135771b7d72365",
+                ),
+                (
+                    fq2(
+                        "0x289b07866f2d2a403dc8bcd8c6a33be53ae3d9163aa78fdb9672340be1d65afe",
+                        "0x1306a5a8af4ff0c3ca99097149576eddca47ceddc3111e06c6eedd71308244bc",
+                    ),
+                    fq2(
+                        "0x191a552811d23a216bc753163b6dac0bc8b96bfbd536cd09cc82fa6a42aebd09",
+                        "0x1bc22b5f7e50520f826e1216cbb2cdcd228cebedb08239322a34238c84f2f72f",
+                    ),
+                ),
+            ),
+            (
+                fq2(
+                    "0xd99f756107fdca5d6192a98054aadfa73a7d1a92c5c214de3f3b23094789f59",
+                    "0x12962fbcb90ae5119d2996b0a2fbd342eef773dfd8db21053e25cc2cdcb7f4f0",
+                ),
+                (
+                    fq2(
+                        "0x23c5cfa3894f31e197ade223fb0403133c51302bf503a9ecea0a7869c9708889",
+                        "0x15cd6833b9ad91bb9de29e8a20bf6d77504e2ed6a3c694a5f15f04d1c307b277",
+                    ),
+                    fq2(
+                        "0x1ab13380d49a779ed624c794e5471a356637dcfde9bf5f874802019f9948ff89",
+                        "0xf9f0dfea24b4516cb255198c5ffd46a9e9d178e16f73b61fe8a1ea30cf62a05",
+                    ),
+                ),
+            ),
+            (
+                fq2(
+                    "0x4316a408308ab1fabf0c0087d263d8ce27901e579bde6ef95c15f41eb6e8a7",
+                    "0x17af8ab8b60770d3c984caeaa37cb8221d74b82f65e8607b1bab32cb6375d5ec",
+                ),
+                (
+                    fq2(
+                        "0x12085cc629789c02c7e57f7810fa9402fefdc8667b8f35546baf4f0db943ac9e",
+                        "0x95235784e54c7c4cf60dd3e923eef7fcf29a63c5a58b4da30d03c2d570c2fb5",
+                    ),
+                    fq2(
+                        "0x2b9b6c3433e8e084fffe1bf649923251849c49ab04aaaa1af0a986b73b70b821",
+                        "0x29420cc411f45b0e7f64fbd2e3f99d87cba4193cde9c6888ee8bf32e69a533b6",
+                    ),
+                ),
+            ),
+        ];
+        for (u, (x, y)) in test_vector {
+            let expected = G2Affine::from_xy(x, y).unwrap();
+            let output = svdw_map_to_curve::<G2>(u, c1, c2, c3, c4, G2::SVDW_Z).unwrap();
+            assert_eq!(output.to_affine(), expected);
+        }
+    }
+
+    #[test]
+    fn test_glv_mul() {
+        crate::tests::curve::glv_mul_test::<G1>();
+        crate::tests::curve::glv_mul_test::<G2>();
     }
 
     #[test]
@@ -1,19 +1,21 @@
 #![allow(clippy::suspicious_arithmetic_impl)]
+use crate::arithmetic::{mul_512, wnaf_add, wnaf_form, wnaf_table};
 use crate::bn256::curve::*;
 use crate::bn256::fq::*;
 use crate::bn256::fq12::*;
 use crate::bn256::fq2::*;
-use crate::bn256::fq6::FROBENIUS_COEFF_FQ6_C1;
+use crate::bn256::fq6::{Fq6, FROBENIUS_COEFF_FQ6_C1};
 use crate::bn256::fr::*;
 use crate::ff::{Field, PrimeField};
 use crate::group::cofactor::CofactorCurveAffine;
-use crate::group::Group;
+use crate::group::{Group, GroupEncoding, UncompressedEncoding};
+use crate::serde::SerdeObject;
 use core::borrow::Borrow;
 use core::iter::Sum;
 use core::ops::{Add, Mul, MulAssign, Neg, Sub};
 use pairing::{Engine, MillerLoopResult, MultiMillerLoop, PairingCurveAffine};
 use rand_core::RngCore;
-use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
 
 pub const BN_X: u64 = 4965661367192848881;
 
@@ -24,6 +26,13 @@ pub const SIX_U_PLUS_2_NAF: [i8; 65] = [
     0, 1, 0, 1, 1,
 ];
 
+// BN_X in NAF form
+const BN_X_NAF: [i8; 63] = [
+    1, 0, 0, 0, -1, 0, 0, 0, 0, 1, 0, 1, 0, 0, 0, 0, 1, 0, 0, 1, 0, -1, 0, 1, 0, 1, 0, 1, 0, 0, 1,
+    0, 0, 0, 1, 0, -1, 0, -1, 0, -1, 0, 1, 0, 1, 0, 0, -1, 0, 1, 0, 1, 0, -1, 0, 0, 1, 0, 1, 0, 0,
+    0, 1,
+];
+
 pub const XI_TO_Q_MINUS_1_OVER_2: Fq2 = Fq2 {
     c0: Fq([
         0xe4bbdd0c2936b629,
@@ -60,6 +69,98 @@ impl PairingCurveAffine for G2Affine {
 #[derive(Copy, Clone, Debug, Default)]
 pub struct Gt(pub(crate) Fq12);
 
+/// The generator of `Gt`, `e(G1::generator(), G2::generator())`.
+const GT_GENERATOR: Gt = Gt(Fq12 {
+    c0: Fq6 {
+        c0: Fq2 {
+            c0: Fq([
+                0xc556f62b2a98671d,
+                0x23a59ac167bcf363,
+                0x5ef208445f5f6f37,
+                0x12adf27ccb29382a,
+            ]),
+            c1: Fq([
+                0x2e02a64acbd60549,
+                0xd618018ea58e4add,
+                0x14d585f1a45ba647,
+                0x1832226987c434fc,
+            ]),
+        },
+        c1: Fq2 {
+            c0: Fq([
+                0x2306e4312363b991,
+                0x465f6072d4023bf4,
+                0xa2ff062a4a77e736,
+                0x076ea6f18435864a,
+            ]),
+            c1: Fq([
+                0x172d1f257a4d598e,
+                0xddf5bc7b7ffb5ac0,
+                0xae0b22c0bbb0f602,
+                0x1b158f3c2fae9b18,
+            ]),
+        },
+        c2: Fq2 {
+            c0: Fq([
+                0x5cf9cc917da86724,
+                0xc799dc487a0b2753,
+                0x0df2027bf1de17a7,
+                0x197cda6cc3e20636,
+            ]),
+            c1: Fq([
+                0xf16c96d081754cdb,
+                0xce0394312bceeb55,
+                0x644e4dcf1f01ff0a,
+                0x0cbea85ee0b236cc,
+            ]),
+        },
+    },
+    c1: Fq6 {
+        c0: Fq2 {
+            c0: Fq([
+                0x1bb0ce0def1b82a1,
+                0x4c4c9fe1cadefa95,
+                0x746d9990cb12b27e,
+                0x13495c08e5d415c5,
+            ]),
+            c1: Fq([
+                0x9458abcb56d24998,
+                0xb17540bd2a9e5adb,
+                0x9a9983c82e401a9f,
+                0x1614817a84c16291,
+            ]),
+        },
+        c1: Fq2 {
+            c0: Fq([
+                0x8975b68a2bab1f9c,
+                0x2fdd826b796e0f35,
+                0x6a90a35fa03dfaa5,
+                0x1ffef4581607fc37,
+            ]),
+            c1: Fq([
+                0x7002907c28ebfe11,
+                0x7b0591d3d080da67,
+                0xde7e5aa2181f138e,
+                0x210e437dfc43d951,
+            ]),
+        },
+        c2: Fq2 {
+            c0: Fq([
+                0x988ae2485b36cf53,
+                0x5091cc0581334e54,
+                0xda7903229312ca0f,
+                0x2a2341538eaee95c,
+            ]),
+            c1: Fq([
+                0xd34bab373157aa84,
+                0x3511ed44fd0d8598,
+                0x67e42a0bc2ced972,
+                0x2b8f1d5dfd20c55b,
+            ]),
+        },
+    },
+});
+
 impl std::fmt::Display for Gt {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{self:?}")
@@ -86,16 +187,239 @@ impl PartialEq for Gt {
     }
 }
 
+// `round(2^256 * m_j / r)`, where `m` is the first row of the inverse of the
+// [`gls_basis`] matrix, for the Babai rounding of `(k, 0, 0, 0)`.
+const GLS_GAMMA: [[u64; 4]; 4] = [
+    [
+        0x2dff291532e42728,
+        0x55b4ca7ba3e5577f,
+        0x9e80318ab0d92b95,
+        0x0000000000000000,
+    ],
+    [
+        0x46f4bda995d51bb1,
+        0x08e5da66fc7184ae,
+        0x9e80318ab0d92b93,
+        0x0000000000000000,
+    ],
+    [
+        0xd91d232ec7e0b3d7,
+        0x0000000000000002,
+        0x0000000000000000,
+        0x0000000000000000,
+    ],
+    [
+        0xc170977dcef3cd3f,
+        0x55b4ca7ba3e5577d,
+        0x9e80318ab0d92b95,
+        0x0000000000000000,
+    ],
+];
+
+// Number of sign-aligned digits of the decomposed scalar, whose components
+// have at most 65 bits.
+const GLS_DIGITS: usize = 66;
+
+// Window of the NAF of the components in `Gt::mul_gls_vartime`.
+const GLS_WINDOW: usize = 4;
+
+/// Short basis of the lattice `{v : v0 + v1 p + v2 p^2 + v3 p^3 = 0 mod r}`
+/// in terms of the BN parameter `x`, with entries below `2^64`. The Frobenius
+/// map raises elements of `Gt` to the power `p`.
+fn gls_basis() -> [[Fr; 4]; 4] {
+    let x = Fr::from(BN_X);
+    let x2 = x.double();
+    [
+        [x2 + Fr::ONE, Fr::ZERO, x2, Fr::ONE],
+        [x2, x + Fr::ONE, -x, x],
+        [x + Fr::ONE, x, x, -x2],
+        [x2 + Fr::ONE, -x, -(x + Fr::ONE), -x],
+    ]
+}
+
 impl Gt {
     /// Returns the group identity, which is $1$.
     pub fn identity() -> Gt {
         Gt(Fq12::ONE)
     }
 
+    /// Returns the generator `e(G1::generator(), G2::generator())`.
+    pub fn generator() -> Gt {
+        GT_GENERATOR
+    }
+
     /// Doubles this group element.
     pub fn double(&self) -> Gt {
         Gt(self.0.square())
     }
+
+    /// Computes `self * scalar` with [`Gt::mul_gls_vartime`].
+    ///
+    /// This function is not constant time: the sequence of group operations
+    /// depends on `scalar`, which must therefore be public. `self` must belong
+    /// to the subgroup of order `r`.
+    pub fn mul_vartime(&self, scalar: &Fr) -> Gt {
+        self.mul_gls_vartime(scalar)
+    }
+
+    /// Computes `self * scalar` with the width-`window` NAF method, using a
+    /// table of `2^(window - 2)` elements.
+    ///
+    /// This function is not constant time: the sequence of group operations
+    /// depends on `scalar`, which must therefore be public.
+    ///
+    /// Panics if `window` is not in `2..=16`.
+    pub fn mul_wnaf(&self, scalar: &Fr, window: usize) -> Gt {
+        crate::arithmetic::wnaf_mul(self, scalar, window)
+    }
+
+    /// Decomposes `k` into four components `(k_i, k_i_neg)` of at most 65
+    /// bits such that `k = sum ±k_i * p^i mod r`, where `k_i` is negated when
+    /// `k_i_neg` is set.
+    pub fn decompose_scalar(k: &Fr) -> [(u128, Choice); 4] {
+        let to_limbs = |e: &Fr| {
+            let repr = e.to_repr();
+            [
+                u64::from_le_bytes(repr[0..8].try_into().unwrap()),
+                u64::from_le_bytes(repr[8..16].try_into().unwrap()),
+                u64::from_le_bytes(repr[16..24].try_into().unwrap()),
+                u64::from_le_bytes(repr[24..32].try_into().unwrap()),
+            ]
+        };
+
+        // Upper half of a 512-bit product, rounded to the nearest integer.
+        let round_high = |c: [u64; 8]| {
+            let (c4, carry) = c[4].overflowing_add(c[3] >> 63);
+            let (c5, carry) = c[5].overflowing_add(carry as u64);
+            let (c6, carry) = c[6].overflowing_add(carry as u64);
+            [c4, c5, c6, c[7] + carry as u64]
+        };
+
+        let input = to_limbs(k);
+        let basis = gls_basis();
+        let mut v = [*k, Fr::ZERO, Fr::ZERO, Fr::ZERO];
+        for (gamma, row) in GLS_GAMMA.iter().zip(basis.iter()) {
+            let c = Fr::from_raw(round_high(mul_512(*gamma, input)));
+            for (v, b) in v.iter_mut().zip(row.iter()) {
+                *v -= c * b;
+            }
+        }
+
+        v.map(|v| {
+            let neg = !v.to_repr()[16..].iter().fold(0, |acc, b| acc | b).ct_eq(&0);
+            let v = Fr::conditional_select(&v, &-v, neg);
+            let e = to_limbs(&v);
+            (u128::from(e[0]) | (u128::from(e[1]) << 64), neg)
+        })
+    }
+
+    /// Returns the bases `±self^(p^i)` of the components of
+    /// [`Gt::decompose_scalar`], computed with the Frobenius map.
+    fn gls_bases(&self, signs: [Choice; 4]) -> [Gt; 4] {
+        let mut bases = [*self; 4];
+        for i in 1..4 {
+            bases[i].0 = bases[i - 1].0;
+            bases[i].0.frobenius_map(1);
+        }
+        for (base, neg) in bases.iter_mut().zip(signs.iter()) {
+            *base = Gt::conditional_select(base, &-*base, *neg);
+        }
+        bases
+    }
+
+    /// Computes `self * k` in the cyclotomic subgroup: `k` is split with
+    /// [`Gt::decompose_scalar`] and the four components are recoded into
+    /// sign-aligned digits, so that every step is one cyclotomic squaring and
+    /// one multiplication by `±b0 * b1^e1 * b2^e2 * b3^e3`, with `e_i` in
+    /// `{0, 1}`, selected from a table of 8 elements.
+    ///
+    /// The sequence of operations does not depend on `k`. `self` must belong
+    /// to the subgroup of order `r`, as the pairing outputs do.
+    pub fn mul_gls(&self, k: &Fr) -> Gt {
+        let ks = Self::decompose_scalar(k);
+        let bases = self.gls_bases(ks.map(|(_, neg)| neg));
+
+        // The recoding needs `k0` odd: otherwise `b0` is divided out at the end.
+        let k0_even = Choice::from(!ks[0].0 as u8 & 1);
+        let k0 = ks[0].0 | u128::from(k0_even.unwrap_u8());
+
+        let mut table = [bases[0]; 8];
+        for (j, base) in bases[1..].iter().enumerate() {
+            for i in 0..1 << j {
+                table[(1 << j) + i] = table[i] + base;
+            }
+        }
+
+        // The digits of `k0` are `±1`, the others are `0` or aligned with them.
+        let mut neg = [Choice::from(0); GLS_DIGITS];
+        let mut idx = [0u8; GLS_DIGITS];
+        for (i, neg) in neg[..GLS_DIGITS - 1].iter_mut().enumerate() {
+            *neg = Choice::from(!(k0 >> (i + 1)) as u8 & 1);
+        }
+        for (j, (mut kj, _)) in ks[1..].iter().copied().enumerate() {
+            for i in 0..GLS_DIGITS {
+                let bit = (kj & 1) as u8;
+                idx[i] |= bit << j;
+                kj = (kj >> 1) + u128::from(bit & neg[i].unwrap_u8());
+            }
+        }
+
+        let mut acc = Gt::identity();
+        for i in (0..GLS_DIGITS).rev() {
+            acc.0.cyclotomic_square();
+            let mut term = table[0];
+            for (j, t) in table.iter().enumerate() {
+                term.conditional_assign(t, idx[i].ct_eq(&(j as u8)));
+            }
+            acc += Gt::conditional_select(&term, &-term, neg[i]);
+        }
+        Gt::conditional_select(&acc, &(acc - bases[0]), k0_even)
+    }
+
+    /// Computes `self * k` in the cyclotomic subgroup from the width-4 NAFs of
+    /// the components of [`Gt::decompose_scalar`], interleaved in a single
+    /// loop of cyclotomic squarings.
+    ///
+    /// This function is not constant time: the sequence of group operations
+    /// depends on `k`, which must therefore be public. `self` must belong to
+    /// the subgroup of order `r`, as the pairing outputs do.
+    pub fn mul_gls_vartime(&self, k: &Fr) -> Gt {
+        let ks = Self::decompose_scalar(k);
+        let nafs = ks.map(|(k, neg)| {
+            let naf = wnaf_form(&k.to_le_bytes(), GLS_WINDOW);
+            if bool::from(neg) {
+                naf.iter().map(|digit| -digit).collect()
+            } else {
+                naf
+            }
+        });
+
+        // The tables of `self^(p^i)` are the Frobenius images of the first one.
+        let mut tables = vec![wnaf_table(self, GLS_WINDOW)];
+        for i in 1..4 {
+            let table = tables[i - 1]
+                .iter()
+                .map(|t| {
+                    let mut t = t.0;
+                    t.frobenius_map(1);
+                    Gt(t)
+                })
+                .collect();
+            tables.push(table);
+        }
+
+        let len = nafs.iter().map(Vec::len).max().unwrap();
+        let mut acc = Gt::identity();
+        for i in (0..len).rev() {
+            acc.0.cyclotomic_square();
+            for (naf, table) in nafs.iter().zip(tables.iter()) {
+                if let Some(&digit) = naf.get(i) {
+                    wnaf_add(&mut acc, table, digit);
+                }
+            }
+        }
+        acc
+    }
 }
 
 impl<'a> Neg for &'a Gt {
@@ -137,6 +461,9 @@ impl<'a, 'b> Sub<&'b Gt> for &'a Gt {
     }
 }
 
+/// Generic square-and-multiply, valid for any element of `Fq12`, including
+/// those built by the unchecked decoders. [`Gt::mul_gls`] is faster for the
+/// elements of the subgroup of order `r`.
 impl<'a, 'b> Mul<&'b Fr> for &'a Gt {
     type Output = Gt;
 
@@ -180,8 +507,8 @@ where
 impl Group for Gt {
     type Scalar = Fr;
 
-    fn random(_: impl RngCore) -> Self {
-        unimplemented!();
+    fn random(mut rng: impl RngCore) -> Self {
+        Self::generator() * Fr::random(&mut rng)
     }
 
     fn identity() -> Self {
@@ -189,7 +516,7 @@ impl Group for Gt {
     }
 
     fn generator() -> Self {
-        unimplemented!();
+        Self::generator()
     }
 
     fn is_identity(&self) -> Choice {
@@ -202,6 +529,262 @@ impl Group for Gt {
     }
 }
 
+/// Size of the compressed encoding of `Gt`, an element of `Fq6`.
+const GT_COMPRESSED_SIZE: usize = 192;
+
+/// Size of the uncompressed encoding of `Gt`, an element of `Fq12`.
+const GT_UNCOMPRESSED_SIZE: usize = 384;
+
+macro_rules! impl_gt_bytes {
+    ($name:ident, $size:ident) => {
+        #[derive(Copy, Clone)]
+        pub struct $name([u8; $size]);
+
+        impl std::fmt::Debug for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                self.0[..].fmt(f)
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                $name([0; $size])
+            }
+        }
+
+        impl AsRef<[u8]> for $name {
+            fn as_ref(&self) -> &[u8] {
+                &self.0
+            }
+        }
+
+        impl AsMut<[u8]> for $name {
+            fn as_mut(&mut self) -> &mut [u8] {
+                &mut self.0
+            }
+        }
+
+        impl ConstantTimeEq for $name {
+            fn ct_eq(&self, other: &Self) -> Choice {
+                self.0.ct_eq(&other.0)
+            }
+        }
+
+        impl Eq for $name {}
+
+        impl PartialEq for $name {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                bool::from(self.ct_eq(other))
+            }
+        }
+    };
+}
+
+impl_gt_bytes!(GtCompressed, GT_COMPRESSED_SIZE);
+impl_gt_bytes!(GtUncompressed, GT_UNCOMPRESSED_SIZE);
+
+impl Gt {
+    /// Returns whether `self` belongs to the subgroup of order `r` of the
+    /// cyclotomic subgroup of `Fq12`, where the pairing takes its values.
+    pub fn is_torsion_free(&self) -> Choice {
+        // The cyclotomic subgroup is the kernel of `f -> f^(p^4 - p^2 + 1)`.
+        let mut fp2 = self.0;
+        fp2.frobenius_map(2);
+        let mut fp4 = fp2;
+        fp4.frobenius_map(2);
+        let is_cyclotomic = (fp4 * self.0).ct_eq(&fp2);
+
+        // In which `f^(r - 1)` is the inverse of `f`, its conjugate, iff `f^r = 1`.
+        let mut acc = Fq12::ONE;
+        for bit in (-Fr::ONE)
+            .to_repr()
+            .iter()
+            .rev()
+            .flat_map(|byte| (0..8).rev().map(move |i| (byte >> i) & 1 == 1))
+        {
+            acc.cyclotomic_square();
+            if bit {
+                acc *= self.0;
+            }
+        }
+        let mut inverse = self.0;
+        inverse.conjugate();
+
+        is_cyclotomic & acc.ct_eq(&inverse) & !self.0.is_zero()
+    }
+}
+
+/// The compressed encoding of `Gt` is that of the algebraic torus `T2`: an
+/// element `g0 + g1 w` of norm 1 over `Fq6`, with `w^2 = v`, is represented
+/// by `c = (1 + g0) / g1`, from which it is recovered as `(c + w) / (c - w)`.
+/// The identity, for which `g1 = 0`, is encoded as zero with the top bit set.
+impl GroupEncoding for Gt {
+    type Repr = GtCompressed;
+
+    fn from_bytes(bytes: &Self::Repr) -> CtOption<Self> {
+        Self::from_bytes_unchecked(bytes).and_then(|f| CtOption::new(f, f.is_torsion_free()))
+    }
+
+    /// The result is only a valid input to [`Gt::mul_gls`] and
+    /// [`Gt::mul_vartime`] if the bytes come from a trusted element of
+    /// `Gt`: they assume the subgroup of order `r`, unlike `Mul`.
+    fn from_bytes_unchecked(bytes: &Self::Repr) -> CtOption<Self> {
+        let mut tmp = bytes.0;
+        let is_identity = Choice::from(tmp[GT_COMPRESSED_SIZE - 1] >> 7);
+        tmp[GT_COMPRESSED_SIZE - 1] &= 0b0111_1111;
+
+        Fq6::from_bytes(&tmp).and_then(|c| {
+            // (c + w) / (c - w) = (c^2 + v + 2 c w) / (c^2 - v), where
+            // `c^2 - v` is not zero as `v` is not a square of `Fq6`.
+            let c2 = c.square();
+            let mut v = Fq6::ONE;
+            v.mul_by_nonresidue();
+            let d = (c2 - v).invert().unwrap_or(Fq6::ZERO);
+            let f = Gt(Fq12 {
+                c0: (c2 + v) * d,
+                c1: c.double() * d,
+            });
+
+            CtOption::new(
+                Gt::conditional_select(&f, &Gt::identity(), is_identity),
+                // If the identity flag is set, `c` should have been zero.
+                !is_identity | c.is_zero(),
+            )
+        })
+    }
+
+    fn to_bytes(&self) -> Self::Repr {
+        let is_identity = self.0.c1.is_zero();
+        let c = (Fq6::ONE + self.0.c0) * self.0.c1.invert().unwrap_or(Fq6::ZERO);
+
+        let mut res = c.to_bytes();
+        res[GT_COMPRESSED_SIZE - 1] |= u8::conditional_select(&0u8, &(1u8 << 7), is_identity);
+        GtCompressed(res)
+    }
+}
+
+/// The uncompressed encoding of `Gt` is that of the underlying `Fq12`.
+impl UncompressedEncoding for Gt {
+    type Uncompressed = GtUncompressed;
+
+    fn from_uncompressed(bytes: &Self::Uncompressed) -> CtOption<Self> {
+        Self::from_uncompressed_unchecked(bytes).and_then(|f| CtOption::new(f, f.is_torsion_free()))
+    }
+
+    /// Only trusted encodings of `Gt` elements give valid inputs to
+    /// [`Gt::mul_gls`] and [`Gt::mul_vartime`], as in `from_bytes_unchecked`.
+    fn from_uncompressed_unchecked(bytes: &Self::Uncompressed) -> CtOption<Self> {
+        Fq12::from_bytes(&bytes.0).map(Gt)
+    }
+
+    fn to_uncompressed(&self) -> Self::Uncompressed {
+        GtUncompressed(self.0.to_bytes())
+    }
+}
+
+impl SerdeObject for Gt {
+    /// As with the other unchecked decoders, the result is only a valid input
+    /// to [`Gt::mul_gls`] and [`Gt::mul_vartime`] for bytes of a trusted `Gt`
+    /// element.
+    fn from_raw_bytes_unchecked(bytes: &[u8]) -> Self {
+        debug_assert_eq!(bytes.len(), GT_UNCOMPRESSED_SIZE);
+        let [c0, c1, c2, c3, c4, c5] =
+            [0, 1, 2, 3, 4, 5].map(|i| Fq2::from_raw_bytes_unchecked(&bytes[i * 64..(i + 1) * 64]));
+        Gt(Fq12 {
+            c0: Fq6 { c0, c1, c2 },
+            c1: Fq6 {
+                c0: c3,
+                c1: c4,
+                c2: c5,
+            },
+        })
+    }
+    fn from_raw_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != GT_UNCOMPRESSED_SIZE {
+            return None;
+        }
+        let [c0, c1, c2, c3, c4, c5] =
+            [0, 1, 2, 3, 4, 5].map(|i| Fq2::from_raw_bytes(&bytes[i * 64..(i + 1) * 64]));
+        let res = Gt(Fq12 {
+            c0: Fq6 {
+                c0: c0?,
+                c1: c1?,
+                c2: c2?,
+            },
+            c1: Fq6 {
+                c0: c3?,
+                c1: c4?,
+                c2: c5?,
+            },
+        });
+        // Check that the element is in the subgroup of order `r`.
+        bool::from(res.is_torsion_free()).then_some(res)
+    }
+    fn to_raw_bytes(&self) -> Vec<u8> {
+        let mut res = Vec::with_capacity(GT_UNCOMPRESSED_SIZE);
+        Self::write_raw(self, &mut res).unwrap();
+        res
+    }
+    /// Only a valid input to [`Gt::mul_gls`] and [`Gt::mul_vartime`] if the
+    /// bytes come from a trusted `Gt` element, see `from_raw_bytes_unchecked`.
+    fn read_raw_unchecked<R: std::io::Read>(reader: &mut R) -> Self {
+        let [c0, c1, c2, c3, c4, c5] = [(); 6].map(|_| Fq2::read_raw_unchecked(reader));
+        Gt(Fq12 {
+            c0: Fq6 { c0, c1, c2 },
+            c1: Fq6 {
+                c0: c3,
+                c1: c4,
+                c2: c5,
+            },
+        })
+    }
+    fn read_raw<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let mut bytes = [0u8; GT_UNCOMPRESSED_SIZE];
+        reader.read_exact(&mut bytes)?;
+        Self::from_raw_bytes(&bytes).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "input is not an element of Gt",
+            )
+        })
+    }
+    fn write_raw<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        for c in [self.0.c0, self.0.c1] {
+            c.c0.write_raw(writer)?;
+            c.c1.write_raw(writer)?;
+            c.c2.write_raw(writer)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "derive_serde")]
+impl ::serde::Serialize for Gt {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let bytes = &self.to_bytes();
+        if serializer.is_human_readable() {
+            ::hex::serde::serialize(&bytes.0, serializer)
+        } else {
+            ::serde_arrays::serialize(&bytes.0, serializer)
+        }
+    }
+}
+
+#[cfg(feature = "derive_serde")]
+impl<'de> ::serde::Deserialize<'de> for Gt {
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use ::serde::de::Error as _;
+        let bytes = if deserializer.is_human_readable() {
+            ::hex::serde::deserialize(deserializer)?
+        } else {
+            ::serde_arrays::deserialize::<_, u8, GT_COMPRESSED_SIZE>(deserializer)?
+        };
+        Option::from(Self::from_bytes(&GtCompressed(bytes)))
+            .ok_or_else(|| D::Error::custom("deserialized bytes don't encode an element of Gt"))
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct G2Prepared {
     pub(crate) coeffs: Vec<(Fq2, Fq2, Fq2)>,
@@ -454,10 +1037,203 @@ impl From<G2Affine> for G2Prepared {
     }
 }
 
-impl MillerLoopResult for Gt {
-    type Gt = Self;
-    // pub fn final_exponentiation(r: &Fq12) -> CtOption<Fq12> {
-    fn final_exponentiation(&self) -> Gt {
+/// Raises an element of the cyclotomic subgroup to the power `BN_X`.
+///
+/// Squarings are done in Karabina's compressed form, and the powers needed
+/// for the nonzero digits of `BN_X_NAF` are decompressed together with one
+/// inversion. Negative digits cost a conjugation, the inverse in the
+/// cyclotomic subgroup.
+fn exp_by_x(f: &mut Fq12) {
+    let digits = || BN_X_NAF.iter().skip(1).filter(|d| **d != 0);
+
+    let mut acc = *f;
+    let mut powers = Vec::with_capacity(digits().count());
+    for d in BN_X_NAF.iter().skip(1) {
+        acc.cyclotomic_square_compressed();
+        if *d != 0 {
+            powers.push(acc);
+        }
+    }
+    Fq12::batch_decompress_cyclotomic(&mut powers);
+
+    let mut res = *f;
+    if BN_X_NAF[0] == -1 {
+        res.conjugate();
+    }
+    for (power, d) in powers.iter_mut().zip(digits()) {
+        if *d == -1 {
+            power.conjugate();
+        }
+        res.mul_assign(power);
+    }
+    *f = res;
+}
+
+impl Gt {
+    /// Raises a Miller loop output to the easy part `(p^6 - 1)(p^2 + 1)` of
+    /// the final exponent, mapping it into the cyclotomic subgroup. Fails when
+    /// `f` is zero.
+    fn final_exponentiation_easy(f: &Fq12) -> CtOption<Fq12> {
+        let mut f1 = *f;
+        f1.conjugate();
+
+        f.invert().map(|mut f2| {
+            let mut r = f1;
+            r.mul_assign(&f2);
+            f2 = r;
+            r.frobenius_map(2);
+            r.mul_assign(&f2);
+            r
+        })
+    }
+
+    /// Raises an element of the cyclotomic subgroup to the hard part
+    /// `(p^4 - p^2 + 1) / r` of the final exponent, with the addition chain of
+    /// Scott et al. (<https://eprint.iacr.org/2008/490.pdf>). The three
+    /// exponentiations by `BN_X` use compressed cyclotomic squarings.
+    fn final_exponentiation_hard(r: &Fq12) -> Fq12 {
+        let r = *r;
+
+        let mut fp = r;
+        fp.frobenius_map(1);
+
+        let mut fp2 = r;
+        fp2.frobenius_map(2);
+        let mut fp3 = fp2;
+        fp3.frobenius_map(1);
+
+        let mut fu = r;
+        exp_by_x(&mut fu);
+
+        let mut fu2 = fu;
+        exp_by_x(&mut fu2);
+
+        let mut fu3 = fu2;
+        exp_by_x(&mut fu3);
+
+        let mut y3 = fu;
+        y3.frobenius_map(1);
+
+        let mut fu2p = fu2;
+        fu2p.frobenius_map(1);
+
+        let mut fu3p = fu3;
+        fu3p.frobenius_map(1);
+
+        let mut y2 = fu2;
+        y2.frobenius_map(2);
+
+        let mut y0 = fp;
+        y0.mul_assign(&fp2);
+        y0.mul_assign(&fp3);
+
+        let mut y1 = r;
+        y1.conjugate();
+
+        let mut y5 = fu2;
+        y5.conjugate();
+
+        y3.conjugate();
+
+        let mut y4 = fu;
+        y4.mul_assign(&fu2p);
+        y4.conjugate();
+
+        let mut y6 = fu3;
+        y6.mul_assign(&fu3p);
+        y6.conjugate();
+
+        y6.cyclotomic_square();
+        y6.mul_assign(&y4);
+        y6.mul_assign(&y5);
+
+        let mut t1 = y3;
+        t1.mul_assign(&y5);
+        t1.mul_assign(&y6);
+
+        y6.mul_assign(&y2);
+
+        t1.cyclotomic_square();
+        t1.mul_assign(&y6);
+        t1.cyclotomic_square();
+
+        let mut t0 = t1;
+        t0.mul_assign(&y1);
+
+        t1.mul_assign(&y0);
+
+        t0.cyclotomic_square();
+        t0.mul_assign(&t1);
+
+        t0
+    }
+
+    /// Raises an element of the cyclotomic subgroup to the multiple
+    /// `2x(6x^2 + 3x + 1) (p^4 - p^2 + 1) / r` of the hard part, with `x =
+    /// BN_X`, following Fuentes-Castañeda et al.
+    /// (<https://eprint.iacr.org/2011/297.pdf>) with the addition chain of
+    /// Duquesne and Ghammam (<https://eprint.iacr.org/2015/192.pdf>,
+    /// Algorithm 6).
+    ///
+    /// The extra factor is coprime to `r`, so the result is the identity
+    /// exactly when the pairing value is, but it is not the pairing value
+    /// itself: this is only meant for [`Bn256::pairing_check`]. The result is
+    /// returned as two cyclotomic factors `(t0, t1)` with `t0 * t1` the
+    /// exponentiation, so that the identity test can compare `t0` against the
+    /// conjugate of `t1` instead of multiplying.
+    fn final_exponentiation_hard_multiple(r: &Fq12) -> (Fq12, Fq12) {
+        // f^(-x)
+        fn exp_by_neg_x(f: &mut Fq12) {
+            exp_by_x(f);
+            f.conjugate();
+        }
+
+        let mut y0 = *r;
+        exp_by_neg_x(&mut y0);
+        let mut y1 = y0;
+        y1.cyclotomic_square();
+        let mut y2 = y1;
+        y2.cyclotomic_square();
+        let mut y3 = y2;
+        y3.mul_assign(&y1);
+        let mut y4 = y3;
+        exp_by_neg_x(&mut y4);
+        let mut y5 = y4;
+        y5.cyclotomic_square();
+        let mut y6 = y5;
+        exp_by_neg_x(&mut y6);
+        y3.conjugate();
+        y6.conjugate();
+        let mut y7 = y6;
+        y7.mul_assign(&y4);
+        let mut y8 = y7;
+        y8.mul_assign(&y3);
+        let mut y9 = y8;
+        y9.mul_assign(&y1);
+        let mut y10 = y8;
+        y10.mul_assign(&y4);
+        let mut y11 = y10;
+        y11.mul_assign(r);
+        let mut y12 = y9;
+        y12.frobenius_map(1);
+        let mut y13 = y12;
+        y13.mul_assign(&y11);
+        y8.frobenius_map(2);
+        let mut y14 = y8;
+        y14.mul_assign(&y13);
+        let mut y15 = *r;
+        y15.conjugate();
+        y15.mul_assign(&y9);
+        y15.frobenius_map(3);
+
+        (y15, y14)
+    }
+
+    /// The hard part as computed before compressed squarings, with plain
+    /// cyclotomic squarings in the exponentiations by `BN_X`. Kept to test
+    /// [`Gt::final_exponentiation_hard`] against.
+    #[cfg(test)]
+    fn final_exponentiation_hard_reference(r: &Fq12) -> Fq12 {
         fn exp_by_x(f: &mut Fq12) {
             let x = BN_X;
             let mut res = Fq12::ONE;
@@ -470,91 +1246,97 @@ impl MillerLoopResult for Gt {
             *f = res;
         }
 
-        let r = self.0;
-        let mut f1 = self.0;
-        f1.conjugate();
+        let r = *r;
 
-        Gt(r.invert()
-            .map(|mut f2| {
-                let mut r = f1;
-                r.mul_assign(&f2);
-                f2 = r;
-                r.frobenius_map(2);
-                r.mul_assign(&f2);
+        let mut fp = r;
+        fp.frobenius_map(1);
 
-                let mut fp = r;
-                fp.frobenius_map(1);
+        let mut fp2 = r;
+        fp2.frobenius_map(2);
+        let mut fp3 = fp2;
+        fp3.frobenius_map(1);
 
-                let mut fp2 = r;
-                fp2.frobenius_map(2);
-                let mut fp3 = fp2;
-                fp3.frobenius_map(1);
+        let mut fu = r;
+        exp_by_x(&mut fu);
 
-                let mut fu = r;
-                exp_by_x(&mut fu);
+        let mut fu2 = fu;
+        exp_by_x(&mut fu2);
 
-                let mut fu2 = fu;
-                exp_by_x(&mut fu2);
+        let mut fu3 = fu2;
+        exp_by_x(&mut fu3);
 
-                let mut fu3 = fu2;
-                exp_by_x(&mut fu3);
+        let mut y3 = fu;
+        y3.frobenius_map(1);
 
-                let mut y3 = fu;
-                y3.frobenius_map(1);
+        let mut fu2p = fu2;
+        fu2p.frobenius_map(1);
 
-                let mut fu2p = fu2;
-                fu2p.frobenius_map(1);
+        let mut fu3p = fu3;
+        fu3p.frobenius_map(1);
 
-                let mut fu3p = fu3;
-                fu3p.frobenius_map(1);
+        let mut y2 = fu2;
+        y2.frobenius_map(2);
 
-                let mut y2 = fu2;
-                y2.frobenius_map(2);
+        let mut y0 = fp;
+        y0.mul_assign(&fp2);
+        y0.mul_assign(&fp3);
 
-                let mut y0 = fp;
-                y0.mul_assign(&fp2);
-                y0.mul_assign(&fp3);
+        let mut y1 = r;
+        y1.conjugate();
 
-                let mut y1 = r;
-                y1.conjugate();
+        let mut y5 = fu2;
+        y5.conjugate();
 
-                let mut y5 = fu2;
-                y5.conjugate();
+        y3.conjugate();
 
-                y3.conjugate();
+        let mut y4 = fu;
+        y4.mul_assign(&fu2p);
+        y4.conjugate();
 
-                let mut y4 = fu;
-                y4.mul_assign(&fu2p);
-                y4.conjugate();
+        let mut y6 = fu3;
+        y6.mul_assign(&fu3p);
+        y6.conjugate();
 
-                let mut y6 = fu3;
-                y6.mul_assign(&fu3p);
-                y6.conjugate();
+        y6.cyclotomic_square();
+        y6.mul_assign(&y4);
+        y6.mul_assign(&y5);
 
-                y6.cyclotomic_square();
-                y6.mul_assign(&y4);
-                y6.mul_assign(&y5);
+        let mut t1 = y3;
+        t1.mul_assign(&y5);
+        t1.mul_assign(&y6);
 
-                let mut t1 = y3;
-                t1.mul_assign(&y5);
-                t1.mul_assign(&y6);
+        y6.mul_assign(&y2);
 
-                y6.mul_assign(&y2);
+        t1.cyclotomic_square();
+        t1.mul_assign(&y6);
+        t1.cyclotomic_square();
 
-                t1.cyclotomic_square();
-                t1.mul_assign(&y6);
-                t1.cyclotomic_square();
+        let mut t0 = t1;
+        t0.mul_assign(&y1);
 
-                let mut t0 = t1;
-                t0.mul_assign(&y1);
+        t1.mul_assign(&y0);
 
-                t1.mul_assign(&y0);
+        t0.cyclotomic_square();
+        t0.mul_assign(&t1);
 
-                t0.cyclotomic_square();
-                t0.mul_assign(&t1);
+        t0
+    }
+
+    /// The final exponentiation as computed before compressed squarings.
+    #[cfg(test)]
+    fn final_exponentiation_reference(&self) -> Gt {
+        Gt(Gt::final_exponentiation_easy(&self.0)
+            .map(|r| Gt::final_exponentiation_hard_reference(&r))
+            .unwrap())
+    }
+}
 
-                t0
-            })
+impl MillerLoopResult for Gt {
+    type Gt = Self;
+    // pub fn final_exponentiation(r: &Fq12) -> CtOption<Fq12> {
+    fn final_exponentiation(&self) -> Gt {
+        Gt(Gt::final_exponentiation_easy(&self.0)
+            .map(|r| Gt::final_exponentiation_hard(&r))
             .unwrap())
     }
 }
@@ -632,6 +1414,39 @@ pub fn pairing(g1: &G1Affine, g2: &G2Affine) -> Gt {
 #[derive(Clone, Debug)]
 pub struct Bn256;
 
+impl Bn256 {
+    /// Checks the pairing product equation `∏ e(P_i, Q_i) == 1`, as pairing
+    /// based verifiers do, without computing the `Gt` value.
+    ///
+    /// All terms share a single Miller loop and final exponentiation. The
+    /// check returns early when the Miller loop output or its image in the
+    /// cyclotomic subgroup is already the identity. Otherwise it raises to a
+    /// multiple of the hard part that is cheaper to compute, which preserves
+    /// the identity since the extra factor is coprime to `r`, and saves the
+    /// last multiplication, since in the cyclotomic subgroup `a * b == 1` if
+    /// and only if `a` equals the conjugate of `b`.
+    ///
+    /// Runs in variable time, so inputs are assumed to be public.
+    pub fn pairing_check(terms: &[(&G1Affine, &G2Prepared)]) -> bool {
+        let f = multi_miller_loop(terms).0;
+        if f == Fq12::ONE {
+            return true;
+        }
+
+        let f = match Option::<Fq12>::from(Gt::final_exponentiation_easy(&f)) {
+            Some(f) => f,
+            None => return false,
+        };
+        if f == Fq12::ONE {
+            return true;
+        }
+
+        let (t0, mut t1) = Gt::final_exponentiation_hard_multiple(&f);
+        t1.conjugate();
+        t0 == t1
+    }
+}
+
 impl Engine for Bn256 {
     type Fr = Fr;
     type G1 = G1;
@@ -714,6 +1529,319 @@ fn test_pairing() {
     }
 }
 
+#[test]
+fn test_pairing_check() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let g1 = |s: Fr| G1Affine::from(G1::generator() * s);
+    let g2 = |s: Fr| G2Prepared::from(G2Affine::from(G2::generator() * s));
+
+    // Trivial products
+    assert!(Bn256::pairing_check(&[]));
+    assert!(Bn256::pairing_check(&[(
+        &G1Affine::identity(),
+        &G2Prepared::from(G2Affine::generator())
+    )]));
+    let p = g1(Fr::random(&mut rng));
+    let q = g2(Fr::random(&mut rng));
+    assert!(Bn256::pairing_check(&[(&p, &q), (&-p, &q)]));
+    assert!(!Bn256::pairing_check(&[(&p, &q)]));
+
+    for _ in 0..10 {
+        let alpha = Fr::random(&mut rng);
+        let beta = Fr::random(&mut rng);
+        let gamma = Fr::random(&mut rng);
+        let delta = Fr::random(&mut rng);
+        let a = Fr::random(&mut rng);
+        let b = Fr::random(&mut rng);
+        let l = Fr::random(&mut rng);
+
+        // Groth16 without public inputs: e(A, B) = e(α, β) e(C, δ)
+        let c = (a * b - alpha * beta) * delta.invert().unwrap();
+        let (pa, qb) = (g1(a), g2(b));
+        let (palpha, qbeta) = (g1(alpha), g2(beta));
+        let (pc, qdelta) = (g1(c), g2(delta));
+        let terms = [(&-pa, &qb), (&palpha, &qbeta), (&pc, &qdelta)];
+        assert!(Bn256::pairing_check(&terms));
+        assert_eq!(
+            multi_miller_loop(&terms).final_exponentiation(),
+            Gt::identity()
+        );
+
+        let pc_bad = g1(c + Fr::one());
+        let terms = [(&-pa, &qb), (&palpha, &qbeta), (&pc_bad, &qdelta)];
+        assert!(!Bn256::pairing_check(&terms));
+
+        // Groth16 with public inputs: e(A, B) = e(α, β) e(L, γ) e(C, δ)
+        let c = (a * b - alpha * beta - l * gamma) * delta.invert().unwrap();
+        let (pl, qgamma) = (g1(l), g2(gamma));
+        let pc = g1(c);
+        let terms = [
+            (&-pa, &qb),
+            (&palpha, &qbeta),
+            (&pl, &qgamma),
+            (&pc, &qdelta),
+        ];
+        assert!(Bn256::pairing_check(&terms));
+        assert_eq!(
+            multi_miller_loop(&terms).final_exponentiation(),
+            Gt::identity()
+        );
+
+        let pl_bad = g1(l + Fr::one());
+        let terms = [
+            (&-pa, &qb),
+            (&palpha, &qbeta),
+            (&pl_bad, &qgamma),
+            (&pc, &qdelta),
+        ];
+        assert!(!Bn256::pairing_check(&terms));
+        assert_ne!(
+            multi_miller_loop(&terms).final_exponentiation(),
+            Gt::identity()
+        );
+    }
+}
+
+#[test]
+fn test_gt() {
+    assert_eq!(
+        Gt::generator(),
+        pairing(&G1Affine::generator(), &G2Affine::generator())
+    );
+
+    // The generator has the prime order `r` of `Fr`.
+    let r = [
+        0x43e1f593f0000001,
+        0x2833e84879b97091,
+        0xb85045b68181585d,
+        0x30644e72e131a029,
+    ];
+    assert_eq!(Gt(Gt::generator().0.pow_vartime(r)), Gt::identity());
+
+    crate::tests::curve::group_tests::<Gt>();
+}
+
+#[test]
+fn test_final_exponentiation() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    // The hard part used by pairing_check is raised to 2x(6x^2 + 3x + 1).
+    let x = Fr::from(BN_X);
+    let s = (x.square() * Fr::from(6) + x * Fr::from(3) + Fr::one()) * x.double();
+
+    for _ in 0..10 {
+        let p = G1Affine::random(&mut rng);
+        let q = G2Prepared::from(G2Affine::random(&mut rng));
+        let f = multi_miller_loop(&[(&p, &q)]);
+        assert_eq!(f.final_exponentiation(), f.final_exponentiation_reference());
+
+        let f = Gt(Fq12::random(&mut rng));
+        let e = f.final_exponentiation();
+        assert_eq!(e, f.final_exponentiation_reference());
+        assert!(bool::from(e.is_torsion_free()));
+
+        let r = Gt::final_exponentiation_easy(&f.0).unwrap();
+        let (mut t0, t1) = Gt::final_exponentiation_hard_multiple(&r);
+        t0.mul_assign(&t1);
+        assert_eq!(Gt(t0), e * s);
+    }
+
+    assert_eq!(
+        BN_X_NAF
+            .iter()
+            .rev()
+            .fold(0i128, |acc, d| acc * 2 + *d as i128),
+        BN_X as i128
+    );
+
+    // exp_by_x against a plain exponentiation in the cyclotomic subgroup
+    let f = Gt::final_exponentiation_easy(&Fq12::random(&mut rng)).unwrap();
+    let mut g = f;
+    exp_by_x(&mut g);
+    assert_eq!(g, f.pow_vartime([BN_X]));
+}
+
+#[test]
+fn test_gt_mul_vartime() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let g = pairing(&G1Affine::generator(), &G2Affine::generator());
+    for _ in 0..100 {
+        let a = Fr::random(&mut rng);
+        let expected = g * a;
+        assert_eq!(g.mul_vartime(&a), expected);
+        for window in 2..=6 {
+            assert_eq!(g.mul_wnaf(&a, window), expected);
+        }
+    }
+    assert_eq!(g.mul_vartime(&Fr::ZERO), Gt::identity());
+    assert_eq!(g.mul_vartime(&-Fr::ONE), -g);
+}
+
+#[test]
+fn test_gt_mul_gls() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    // The Frobenius map raises elements of `Gt` to the power `p mod r`.
+    let lambda = Fr::from_raw([0xf83e9682e87cfd46, 0x6f4d8248eeb859fb, 0, 0]);
+    let g = Gt::random(&mut rng);
+    let mut frobenius = g.0;
+    frobenius.frobenius_map(1);
+    assert_eq!(
+        frobenius,
+        g.0.pow_vartime([0xf83e9682e87cfd46, 0x6f4d8248eeb859fb])
+    );
+
+    let scalars = [
+        Fr::ZERO,
+        Fr::ONE,
+        Fr::from(2),
+        -Fr::ONE,
+        -Fr::from(2),
+        lambda,
+        Fr::from_u128(u128::MAX),
+    ];
+    let random = (0..100).map(|_| Fr::random(&mut rng));
+    for k in scalars.into_iter().chain(random) {
+        let ks = Gt::decompose_scalar(&k);
+        let mut sum = Fr::ZERO;
+        for (k_i, neg) in ks.iter().rev() {
+            assert!(*k_i < 1 << 65);
+            let k_i = Fr::from_u128(*k_i);
+            sum = sum * lambda + Fr::conditional_select(&k_i, &-k_i, *neg);
+        }
+        assert_eq!(sum, k);
+
+        let expected = Gt(g.0.pow_vartime(
+            k.to_repr()
+                .chunks(8)
+                .map(|limb| u64::from_le_bytes(limb.try_into().unwrap()))
+                .collect::<Vec<_>>(),
+        ));
+        assert_eq!(g * k, expected);
+        assert_eq!(g.mul_gls(&k), expected);
+        assert_eq!(g.mul_gls_vartime(&k), expected);
+    }
+}
+
+#[test]
+fn test_gt_mul_unchecked() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    // An element of `Fq12` outside of `Gt` goes through the unchecked decoder,
+    // and `Mul` still computes its power.
+    let f = Fq12::random(&mut rng);
+    let bytes = GtUncompressed(f.to_bytes());
+    assert!(bool::from(Gt::from_uncompressed(&bytes).is_none()));
+    let g = Gt::from_uncompressed_unchecked(&bytes).unwrap();
+    assert!(!bool::from(g.is_torsion_free()));
+
+    for _ in 0..10 {
+        let k = Fr::random(&mut rng);
+        let expected = Gt(f.pow_vartime(
+            k.to_repr()
+                .chunks(8)
+                .map(|limb| u64::from_le_bytes(limb.try_into().unwrap()))
+                .collect::<Vec<_>>(),
+        ));
+        assert_eq!(g * k, expected);
+    }
+}
+
+#[test]
+fn test_gt_serialization() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let elements = [Gt::identity(), Gt::generator(), -Gt::generator()];
+    let random = (0..20).map(|_| Gt::random(&mut rng));
+    for f in elements.into_iter().chain(random) {
+        assert!(bool::from(f.is_torsion_free()));
+
+        let bytes = f.to_bytes();
+        assert_eq!(bytes.as_ref().len(), 192);
+        assert_eq!(Gt::from_bytes(&bytes).unwrap(), f);
+        assert_eq!(Gt::from_bytes_unchecked(&bytes).unwrap(), f);
+
+        let bytes = f.to_uncompressed();
+        assert_eq!(bytes.as_ref().len(), 384);
+        assert_eq!(Gt::from_uncompressed(&bytes).unwrap(), f);
+        assert_eq!(Gt::from_uncompressed_unchecked(&bytes).unwrap(), f);
+
+        let bytes = f.to_raw_bytes();
+        assert_eq!(Gt::from_raw_bytes(&bytes).unwrap(), f);
+        assert_eq!(Gt::from_raw_bytes_unchecked(&bytes), f);
+        let mut buf = Vec::new();
+        f.write_raw(&mut buf).unwrap();
+        assert_eq!(Gt::read_raw(&mut &buf[..]).unwrap(), f);
+        assert_eq!(Gt::read_raw_unchecked(&mut &buf[..]), f);
+
+        #[cfg(feature = "derive_serde")]
+        {
+            let bytes = bincode::serialize(&f).unwrap();
+            assert_eq!(bincode::deserialize::<Gt>(&bytes).unwrap(), f);
+            let json = serde_json::to_string(&f).unwrap();
+            assert_eq!(serde_json::from_str::<Gt>(&json).unwrap(), f);
+        }
+    }
+
+    for _ in 0..20 {
+        // An element of `Fq12` and one of the cyclotomic subgroup, raised to
+        // the power `(p^6 - 1)(p^2 + 1)`, are not in the subgroup of order `r`.
+        let f = Gt(Fq12::random(&mut rng));
+        let mut g = f.0;
+        g.conjugate();
+        g *= f.0.invert().unwrap();
+        let mut g2 = g;
+        g2.frobenius_map(2);
+        let g = Gt(g * g2);
+
+        for f in [f, g] {
+            assert!(!bool::from(f.is_torsion_free()));
+            let bytes = f.to_uncompressed();
+            assert!(bool::from(Gt::from_uncompressed(&bytes).is_none()));
+            assert_eq!(Gt::from_uncompressed_unchecked(&bytes).unwrap(), f);
+            assert!(Gt::from_raw_bytes(&f.to_raw_bytes()).is_none());
+            assert!(Gt::read_raw(&mut &f.to_raw_bytes()[..]).is_err());
+        }
+
+        // The torus representation recovers the elements of norm 1.
+        let bytes = g.to_bytes();
+        assert!(bool::from(Gt::from_bytes(&bytes).is_none()));
+        assert_eq!(Gt::from_bytes_unchecked(&bytes).unwrap(), g);
+    }
+
+    // The identity flag is only valid with a zero encoding, and the
+    // coefficients must be canonical.
+    let mut bytes = Gt::generator().to_bytes();
+    bytes.as_mut()[191] |= 1 << 7;
+    assert!(bool::from(Gt::from_bytes_unchecked(&bytes).is_none()));
+    let bytes = GtCompressed([0xff; 192]);
+    assert!(bool::from(Gt::from_bytes_unchecked(&bytes).is_none()));
+    let bytes = GtUncompressed([0xff; 384]);
+    assert!(bool::from(
+        Gt::from_uncompressed_unchecked(&bytes).is_none()
+    ));
+}
+
 #[test]
 fn random_bilinearity_tests() {
     let mut rng = XorShiftRng::from_seed([
@@ -1,7 +1,7 @@
 use super::fq::Fq;
 use super::fq2::Fq2;
 use super::fq6::Fq6;
-use crate::ff::Field;
+use crate::ff::{BatchInvert, Field};
 use core::ops::{Add, Mul, Neg, Sub};
 use rand::RngCore;
 use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
@@ -98,6 +98,33 @@ impl Fq12 {
         }
     }
 
+    pub const fn size() -> usize {
+        384
+    }
+
+    /// Attempts to convert the little-endian byte representations of `c0`
+    /// and `c1` into a `Fq12`, failing if any of them is not canonical.
+    pub fn from_bytes(bytes: &[u8; 384]) -> CtOption<Fq12> {
+        let c0 = Fq6::from_bytes(bytes[0..192].try_into().unwrap());
+        let c1 = Fq6::from_bytes(bytes[192..384].try_into().unwrap());
+        CtOption::new(
+            Fq12 {
+                c0: c0.unwrap_or(Fq6::ZERO),
+                c1: c1.unwrap_or(Fq6::ZERO),
+            },
+            c0.is_some() & c1.is_some(),
+        )
+    }
+
+    /// Converts an element of `Fq12` into the little-endian byte
+    /// representations of `c0` and `c1`.
+    pub fn to_bytes(&self) -> [u8; 384] {
+        let mut res = [0u8; 384];
+        res[0..192].copy_from_slice(&self.c0.to_bytes());
+        res[192..384].copy_from_slice(&self.c1.to_bytes());
+        res
+    }
+
     pub fn mul_assign(&mut self, other: &Self) {
         let t0 = self.c0 * other.c0;
         let mut t1 = self.c1 * other.c1;
@@ -291,6 +318,102 @@ impl Fq12 {
         t2.double_assign();
         self.c0.c2 = t2 + t5;
     }
+
+    /// Squares an element of the cyclotomic subgroup in Karabina's compressed
+    /// representation (<https://eprint.iacr.org/2010/542.pdf>, Theorem 3.2).
+    ///
+    /// Writing the element as `g0 + g1 v + g2 v^2 + (g3 + g4 v + g5 v^2) w`,
+    /// only `g1`, `g2`, `g3` and `g5` are read and updated; `g0` and `g4` are
+    /// left stale until [`Fq12::batch_decompress_cyclotomic`] is called.
+    pub fn cyclotomic_square_compressed(&mut self) {
+        let g1 = self.c0.c1;
+        let g2 = self.c0.c2;
+        let g3 = self.c1.c0;
+        let g5 = self.c1.c2;
+
+        let g1_sq = g1.square();
+        let g5_sq = g5.square();
+        let g2_sq = g2.square();
+        let g3_sq = g3.square();
+
+        // 2 * g1 * g5
+        let g1g5 = (g1 + g5).square() - g1_sq - g5_sq;
+        // 2 * g2 * g3
+        let g2g3 = (g2 + g3).square() - g2_sq - g3_sq;
+
+        // g3' = 3 * ξ * (2 * g1 * g5) + 2 * g3
+        let mut t0 = g1g5;
+        t0.mul_by_nonresidue();
+        let mut t1 = t0 + g3;
+        t1.double_assign();
+        self.c1.c0 = t1 + t0;
+
+        // g2' = 3 * (ξ * g5^2 + g1^2) - 2 * g2
+        let mut t0 = g5_sq;
+        t0.mul_by_nonresidue();
+        t0 += g1_sq;
+        let mut t1 = t0 - g2;
+        t1.double_assign();
+        self.c0.c2 = t1 + t0;
+
+        // g1' = 3 * (g3^2 + ξ * g2^2) - 2 * g1
+        let mut t0 = g2_sq;
+        t0.mul_by_nonresidue();
+        t0 += g3_sq;
+        let mut t1 = t0 - g1;
+        t1.double_assign();
+        self.c0.c1 = t1 + t0;
+
+        // g5' = 3 * (2 * g2 * g3) + 2 * g5
+        let mut t1 = g2g3 + g5;
+        t1.double_assign();
+        self.c1.c2 = t1 + g2g3;
+    }
+
+    /// Recovers `g0` and `g4` of cyclotomic elements squared with
+    /// [`Fq12::cyclotomic_square_compressed`], sharing a single inversion
+    /// across all of them.
+    pub fn batch_decompress_cyclotomic(elements: &mut [Fq12]) {
+        let mut nums = Vec::with_capacity(elements.len());
+        let mut dens = Vec::with_capacity(elements.len());
+        for e in elements.iter() {
+            let (g1, g2, g3, g5) = (e.c0.c1, e.c0.c2, e.c1.c0, e.c1.c2);
+            if bool::from(g3.is_zero()) {
+                // g4 = 2 * g1 * g5 / g2
+                nums.push((g1 * g5).double());
+                dens.push(g2);
+            } else {
+                // g4 = (ξ * g5^2 + 3 * g1^2 - 2 * g2) / (4 * g3)
+                let g1_sq = g1.square();
+                let mut num = g5.square();
+                num.mul_by_nonresidue();
+                num += (g1_sq - g2).double() + g1_sq;
+                nums.push(num);
+                dens.push(g3.double().double());
+            }
+        }
+
+        dens.iter_mut().batch_invert();
+
+        for ((e, num), den) in elements.iter_mut().zip(nums.iter()).zip(dens.iter()) {
+            if bool::from(e.c1.c0.is_zero() & e.c0.c2.is_zero()) {
+                *e = Fq12::one();
+                continue;
+            }
+
+            let (g1, g2, g3, g5) = (e.c0.c1, e.c0.c2, e.c1.c0, e.c1.c2);
+            let g4 = num * den;
+
+            // g0 = ξ * (2 * g4^2 + g3 * g5 - 3 * g1 * g2) + 1
+            let g1g2 = g1 * g2;
+            let mut g0 = (g4.square() - g1g2).double() - g1g2 + g3 * g5;
+            g0.mul_by_nonresidue();
+            g0 += Fq2::one();
+
+            e.c0.c0 = g0;
+            e.c1.c1 = g4;
+        }
+    }
 }
 
 impl Field for Fq12 {
@@ -569,6 +692,46 @@ fn test_squaring() {
     }
 }
 
+#[test]
+fn test_cyclotomic_square_compressed() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for _ in 0..100 {
+        // Map a random element into the cyclotomic subgroup with the easy part
+        // of the final exponentiation: f^((p^6 - 1)(p^2 + 1)).
+        let f = Fq12::random(&mut rng);
+        let mut a = f;
+        a.conjugate();
+        a.mul_assign(&f.invert().unwrap());
+        let mut b = a;
+        b.frobenius_map(2);
+        a.mul_assign(&b);
+
+        let mut expected = Vec::with_capacity(8);
+        let mut compressed = Vec::with_capacity(8);
+        let mut b = a;
+        let mut c = a;
+        for _ in 0..8 {
+            b.cyclotomic_square();
+            assert_eq!(b, a.pow_vartime([1 << (expected.len() + 1)]));
+            c.cyclotomic_square_compressed();
+            expected.push(b);
+            compressed.push(c);
+        }
+        Fq12::batch_decompress_cyclotomic(&mut compressed);
+        assert_eq!(compressed, expected);
+    }
+
+    let mut one = Fq12::one();
+    one.cyclotomic_square_compressed();
+    let mut elements = [one];
+    Fq12::batch_decompress_cyclotomic(&mut elements);
+    assert_eq!(elements[0], Fq12::one());
+}
+
 #[test]
 fn test_frobenius() {
     let mut rng = XorShiftRng::from_seed([
@@ -180,8 +180,8 @@ impl Fq2 {
         let c1 = Fq::from_bytes(bytes[32..64].try_into().unwrap());
         CtOption::new(
             Fq2 {
-                c0: c0.unwrap(),
-                c1: c1.unwrap(),
+                c0: c0.unwrap_or(Fq::zero()),
+                c1: c1.unwrap_or(Fq::zero()),
             },
             c0.is_some() & c1.is_some(),
         )
@@ -488,8 +488,14 @@ impl PrimeField for Fq2 {
         Fq2Bytes(self.to_bytes())
     }
 
+    /// Returns `sgn0` of the element as defined in
+    /// https://datatracker.ietf.org/doc/html/rfc9380#section-4.1, that is the
+    /// parity of `c0`, or of `c1` when `c0` is zero.
     fn is_odd(&self) -> Choice {
-        Choice::from(self.to_repr().as_ref()[0] & 1)
+        let sign_0 = self.c0.is_odd();
+        let zero_0 = self.c0.is_zero();
+        let sign_1 = self.c1.is_odd();
+        sign_0 | (zero_0 & sign_1)
     }
 }
 
@@ -498,6 +504,15 @@ impl FromUniformBytes<64> for Fq2 {
         Self::new(Fq::from_uniform_bytes(bytes), Fq::zero())
     }
 }
+
+impl FromUniformBytes<128> for Fq2 {
+    /// Reduces `c0` from the first 64 bytes and `c1` from the last 64 bytes.
+    fn from_uniform_bytes(bytes: &[u8; 128]) -> Self {
+        let c0 = Fq::from_uniform_bytes(bytes[..64].try_into().unwrap());
+        let c1 = Fq::from_uniform_bytes(bytes[64..].try_into().unwrap());
+        Self::new(c0, c1)
+    }
+}
 #[derive(Clone, Copy, Debug)]
 pub struct Fq2Bytes([u8; 64]);
 
@@ -779,6 +794,40 @@ fn test_frobenius() {
     }
 }
 
+#[test]
+fn test_is_odd() {
+    use rand_core::OsRng;
+
+    // `sgn0` of RFC 9380: the parity of `c0`, or of `c1` when `c0` is zero.
+    let one = Fq::one();
+    let two = one.double();
+    for (c0, c1, odd) in [
+        (Fq::zero(), Fq::zero(), false),
+        (one, Fq::zero(), true),
+        (two, Fq::zero(), false),
+        (one, one, true),
+        (one, two, true),
+        (two, one, false),
+        (two, two, false),
+        (Fq::zero(), one, true),
+        (Fq::zero(), two, false),
+        (Fq::zero(), -one, false),
+        (Fq::zero(), -two, true),
+    ] {
+        assert_eq!(bool::from(Fq2::new(c0, c1).is_odd()), odd);
+    }
+
+    for _ in 0..100 {
+        let a = Fq2::random(OsRng);
+        assert_eq!(bool::from(a.is_odd()), bool::from(a.c0.is_odd()));
+        let b = Fq2::new(Fq::zero(), a.c1);
+        assert_eq!(bool::from(b.is_odd()), bool::from(a.c1.is_odd()));
+        if !bool::from(a.is_zero()) {
+            assert_ne!(bool::from(a.is_odd()), bool::from((-a).is_odd()));
+        }
+    }
+}
+
 #[test]
 fn test_zeta() {
     let zeta = Fq2::new(Fq::ZETA.square(), Fq::zero());
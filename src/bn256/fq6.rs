@@ -101,6 +101,36 @@ impl Fq6 {
         }
     }
 
+    pub const fn size() -> usize {
+        192
+    }
+
+    /// Attempts to convert the little-endian byte representations of `c0`,
+    /// `c1` and `c2` into a `Fq6`, failing if any of them is not canonical.
+    pub fn from_bytes(bytes: &[u8; 192]) -> CtOption<Fq6> {
+        let c0 = Fq2::from_bytes(bytes[0..64].try_into().unwrap());
+        let c1 = Fq2::from_bytes(bytes[64..128].try_into().unwrap());
+        let c2 = Fq2::from_bytes(bytes[128..192].try_into().unwrap());
+        CtOption::new(
+            Fq6 {
+                c0: c0.unwrap_or(Fq2::ZERO),
+                c1: c1.unwrap_or(Fq2::ZERO),
+                c2: c2.unwrap_or(Fq2::ZERO),
+            },
+            c0.is_some() & c1.is_some() & c2.is_some(),
+        )
+    }
+
+    /// Converts an element of `Fq6` into the little-endian byte
+    /// representations of `c0`, `c1` and `c2`.
+    pub fn to_bytes(&self) -> [u8; 192] {
+        let mut res = [0u8; 192];
+        res[0..64].copy_from_slice(&self.c0.to_bytes());
+        res[64..128].copy_from_slice(&self.c1.to_bytes());
+        res[128..192].copy_from_slice(&self.c2.to_bytes());
+        res
+    }
+
     pub fn mul_assign(&mut self, other: &Self) {
         let mut a_a = self.c0;
         let mut b_b = self.c1;
@@ -2,7 +2,7 @@
 macro_rules! endo {
     ($name:ident, $field:ident, $params:expr) => {
         impl CurveEndo for $name {
-            fn decompose_scalar(k: &$field) -> (u128, bool, u128, bool) {
+            fn decompose_scalar(k: &$field) -> (u128, Choice, u128, Choice) {
                 let to_limbs = |e: &$field| {
                     let repr = e.to_repr();
                     let repr = repr.as_ref();
@@ -24,14 +24,27 @@ macro_rules! endo {
                     let (_, borrow) = sbb(0xffffffffffffffff, e[1], borrow);
                     let (_, borrow) = sbb(0xffffffffffffffff, e[2], borrow);
                     let (_, borrow) = sbb(0x00, e[3], borrow);
-                    borrow & 1 != 0
+                    Choice::from((borrow & 1) as u8)
+                };
+
+                // Upper half of a 512-bit product, rounded to the nearest integer.
+                //
+                // Rounding `c1` and `c2` instead of truncating them is Babai's
+                // rounding, which keeps both halves within half a basis vector of
+                // zero. The secp256k1 and secq256k1 bases need it for the halves to
+                // stay below `2^128`. For the other curves it can move a
+                // decomposition by one basis vector, which keeps it valid and below
+                // `2^128`, as `test_endo` checks for every curve.
+                let round_high = |c: [u64; 8]| {
+                    let (c4, carry) = c[4].overflowing_add(c[3] >> 63);
+                    let (c5, carry) = c[5].overflowing_add(carry as u64);
+                    let (c6, carry) = c[6].overflowing_add(carry as u64);
+                    [c4, c5, c6, c[7] + carry as u64]
                 };
 
                 let input = to_limbs(&k);
-                let c1 = mul_512($params.gamma2, input);
-                let c2 = mul_512($params.gamma1, input);
-                let c1 = [c1[4], c1[5], c1[6], c1[7]];
-                let c2 = [c2[4], c2[5], c2[6], c2[7]];
+                let c1 = round_high(mul_512($params.gamma2, input));
+                let c2 = round_high(mul_512($params.gamma1, input));
                 let q1 = mul_512(c1, $params.b1);
                 let q2 = mul_512(c2, $params.b2);
                 let q1 = $field::from_raw([q1[0], q1[1], q1[2], q1[3]]);
@@ -40,8 +53,8 @@ macro_rules! endo {
                 let k1 = k + k2 * $field::ZETA;
                 let k1_neg = is_neg(&k1);
                 let k2_neg = is_neg(&k2);
-                let k1 = if k1_neg { -k1 } else { k1 };
-                let k2 = if k2_neg { -k2 } else { k2 };
+                let k1 = $field::conditional_select(&k1, &-k1, k1_neg);
+                let k2 = $field::conditional_select(&k2, &-k2, k2_neg);
 
                 (get_lower_128(&k1), k1_neg, get_lower_128(&k2), k2_neg)
             }
@@ -62,14 +75,19 @@ macro_rules! new_curve_impl {
     $constant_b:expr,
     $curve_id:literal,
     $hash_to_curve:expr,
+    $($custom_encoding:ident,)?
     ) => {
 
+        paste::paste! {
+            #[allow(non_upper_case_globals, dead_code)]
+            const [< $name _COMPRESSED_SIZE >]: usize = if $flags_extra_byte {$base::size() + 1} else {$base::size()};
+        }
+
+        #[allow(unused_macros)]
         macro_rules! impl_compressed {
             () => {
                 paste::paste! {
 
-                #[allow(non_upper_case_globals)]
-                const [< $name _COMPRESSED_SIZE >]: usize = if $flags_extra_byte {$base::size() + 1} else {$base::size()};
                 #[derive(Copy, Clone, PartialEq, Eq)]
                 #[cfg_attr(feature = "derive_serde", derive(Serialize, Deserialize))]
                 pub struct [<$name Compressed >](#[cfg_attr(feature = "derive_serde", serde(with = "serde_arrays"))] [u8; [< $name _COMPRESSED_SIZE >]]);
@@ -214,7 +232,7 @@ macro_rules! new_curve_impl {
                         }
                     }
 
-                    impl Eq for [< $name Uncompressed >] {}
+                    impl cmp::Eq for [< $name Uncompressed >] {}
 
                     impl PartialEq for [< $name Uncompressed >] {
                         #[inline]
@@ -293,13 +311,16 @@ macro_rules! new_curve_impl {
         #[cfg(feature = "derive_serde")]
         macro_rules! serialize_deserialize_to_from_bytes {
             () => {
-                impl ::serde::Serialize for $name {
-                    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-                        let bytes = &self.to_bytes();
-                        if serializer.is_human_readable() {
-                            ::hex::serde::serialize(&bytes.0, serializer)
-                        } else {
-                            ::serde_arrays::serialize(&bytes.0, serializer)
+                paste::paste! {
+                    impl ::serde::Serialize for $name {
+                        fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                            let mut bytes = [0u8; [< $name _COMPRESSED_SIZE >]];
+                            bytes.copy_from_slice(self.to_bytes().as_ref());
+                            if serializer.is_human_readable() {
+                                ::hex::serde::serialize(&bytes, serializer)
+                            } else {
+                                ::serde_arrays::serialize(&bytes, serializer)
+                            }
                         }
                     }
                 }
@@ -315,20 +336,25 @@ macro_rules! new_curve_impl {
                             } else {
                                 ::serde_arrays::deserialize::<_, u8, [< $name _COMPRESSED_SIZE >]>(deserializer)?
                             };
-                            Option::from(Self::from_bytes(&[< $name Compressed >](bytes))).ok_or_else(|| {
+                            let mut repr = <Self as group::GroupEncoding>::Repr::default();
+                            repr.as_mut().copy_from_slice(&bytes);
+                            Option::from(Self::from_bytes(&repr)).ok_or_else(|| {
                                 D::Error::custom("deserialized bytes don't encode a valid field element")
                             })
                         }
                     }
                 }
 
-                impl ::serde::Serialize for $name_affine {
-                    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-                        let bytes = &self.to_bytes();
-                        if serializer.is_human_readable() {
-                            ::hex::serde::serialize(&bytes.0, serializer)
-                        } else {
-                            ::serde_arrays::serialize(&bytes.0, serializer)
+                paste::paste! {
+                    impl ::serde::Serialize for $name_affine {
+                        fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                            let mut bytes = [0u8; [< $name _COMPRESSED_SIZE >]];
+                            bytes.copy_from_slice(self.to_bytes().as_ref());
+                            if serializer.is_human_readable() {
+                                ::hex::serde::serialize(&bytes, serializer)
+                            } else {
+                                ::serde_arrays::serialize(&bytes, serializer)
+                            }
                         }
                     }
                 }
@@ -344,7 +370,9 @@ macro_rules! new_curve_impl {
                             } else {
                                 ::serde_arrays::deserialize::<_, u8, [< $name _COMPRESSED_SIZE >]>(deserializer)?
                             };
-                            Option::from(Self::from_bytes(&[< $name Compressed >](bytes))).ok_or_else(|| {
+                            let mut repr = <Self as group::GroupEncoding>::Repr::default();
+                            repr.as_mut().copy_from_slice(&bytes);
+                            Option::from(Self::from_bytes(&repr)).ok_or_else(|| {
                                 D::Error::custom("deserialized bytes don't encode a valid field element")
                             })
                         }
@@ -369,7 +397,16 @@ macro_rules! new_curve_impl {
         #[cfg(feature = "derive_serde")]
         serialize_deserialize_to_from_bytes!();
 
-        impl_compressed!();
+        // Curves passing `custom_encoding` implement `group::GroupEncoding`
+        // themselves, e.g. to stay compatible with an existing format.
+        macro_rules! impl_encoding {
+            () => {
+                impl_compressed!();
+            };
+            (custom_encoding) => {};
+        }
+
+        impl_encoding!($($custom_encoding)?);
         impl_uncompressed!();
 
 
@@ -386,7 +423,7 @@ macro_rules! new_curve_impl {
 
             #[inline]
             fn curve_constant_3b() -> $base {
-                lazy_static::lazy_static! {
+                $crate::lazy_static::lazy_static! {
                     static ref CONST_3B: $base = $constant_b + $constant_b + $constant_b;
                 }
                 *CONST_3B
@@ -580,6 +617,59 @@ macro_rules! new_curve_impl {
             type AffineRepr = $name_affine;
 
             fn batch_normalize(p: &[Self], q: &mut [Self::AffineRepr]) {
+                $crate::batch_normalize_with(p, q, $name::batch_normalize_serial);
+            }
+
+            fn to_affine(&self) -> Self::AffineRepr {
+                let zinv = self.z.invert().unwrap_or($base::zero());
+                let x = self.x * zinv;
+                let y = self.y * zinv;
+                let tmp = $name_affine {
+                    x,
+                    y,
+                };
+                $name_affine::conditional_select(&tmp, &$name_affine::identity(), zinv.is_zero())
+            }
+        }
+
+        impl $name {
+            /// Returns the fixed-base table of the generator, built on first use.
+            pub fn generator_table() -> &'static $crate::fixed_base::FixedBaseTable<$name_affine> {
+                $crate::lazy_static::lazy_static! {
+                    static ref GENERATOR_TABLE: $crate::fixed_base::FixedBaseTable<$name_affine> =
+                        $crate::fixed_base::FixedBaseTable::new(&$name_affine::generator());
+                }
+                &GENERATOR_TABLE
+            }
+
+            /// Computes `generator() * scalar` in constant time with
+            /// [`Self::generator_table`].
+            pub fn mul_generator(scalar: &$scalar) -> Self {
+                Self::generator_table().mul(scalar)
+            }
+
+            /// Computes `self * scalar` with the width-4 NAF method.
+            ///
+            /// This function is not constant time: the sequence of group
+            /// operations depends on `scalar`, which must therefore be public.
+            pub fn mul_vartime(&self, scalar: &$scalar) -> Self {
+                self.mul_wnaf(scalar, 4)
+            }
+
+            /// Computes `self * scalar` with the width-`window` NAF method,
+            /// using a table of `2^(window - 2)` points.
+            ///
+            /// This function is not constant time: the sequence of group
+            /// operations depends on `scalar`, which must therefore be public.
+            ///
+            /// Panics if `window` is not in `2..=16`.
+            pub fn mul_wnaf(&self, scalar: &$scalar, window: usize) -> Self {
+                $crate::wnaf_mul(self, scalar, window)
+            }
+
+            /// Converts a batch of projective points into affine form on the
+            /// current thread, sharing a single field inversion.
+            pub fn batch_normalize_serial(p: &[Self], q: &mut [$name_affine]) {
                 assert_eq!(p.len(), q.len());
 
                 let mut acc = $base::one();
@@ -611,17 +701,6 @@ macro_rules! new_curve_impl {
                     *q = $name_affine::conditional_select(&q, &$name_affine::identity(), skip);
                 }
             }
-
-            fn to_affine(&self) -> Self::AffineRepr {
-                let zinv = self.z.invert().unwrap_or($base::zero());
-                let x = self.x * zinv;
-                let y = self.y * zinv;
-                let tmp = $name_affine {
-                    x,
-                    y,
-                };
-                $name_affine::conditional_select(&tmp, &$name_affine::identity(), zinv.is_zero())
-            }
         }
 
         impl group::Group for $name {
@@ -951,6 +1030,16 @@ macro_rules! new_curve_impl {
         }
 
 
+        impl $crate::CurveAffineExt for $name_affine {
+            fn into_coordinates(self) -> ($base, $base) {
+                (self.x, self.y)
+            }
+
+            fn from_coordinates_unchecked(x: $base, y: $base) -> Self {
+                $name_affine { x, y }
+            }
+        }
+
         impl_binops_additive!($name, $name);
         impl_binops_additive!($name, $name_affine);
         impl_binops_additive_specify_output!($name_affine, $name_affine, $name);
@@ -0,0 +1,131 @@
+//! Fixed-base scalar multiplication with precomputed tables.
+//!
+//! A [`FixedBaseTable`] stores, for every `w`-bit window of the scalar, all
+//! the multiples of the base that the window can select. A multiplication is
+//! then one table lookup and one mixed addition per window, without any
+//! doubling. Lookups scan the whole row of the window, so the sequence of
+//! operations and memory accesses does not depend on the scalar.
+
+use ff::PrimeField;
+use group::{Curve, Group};
+use pasta_curves::arithmetic::CurveAffine;
+use subtle::ConstantTimeEq;
+
+/// Window width used by [`FixedBaseTable::new`].
+pub const DEFAULT_WINDOW: usize = 4;
+
+/// Precomputed multiples of a fixed point.
+#[derive(Clone, Debug)]
+pub struct FixedBaseTable<C: CurveAffine> {
+    window: usize,
+    // rows[i][j] = j * 2^(i * window) * base
+    rows: Vec<Vec<C>>,
+}
+
+impl<C: CurveAffine> FixedBaseTable<C> {
+    /// Builds the table of `base` with a window of [`DEFAULT_WINDOW`] bits.
+    pub fn new(base: &C) -> Self {
+        Self::with_window(base, DEFAULT_WINDOW)
+    }
+
+    /// Builds the table of `base` with a window of `window` bits. The table
+    /// holds `ceil(NUM_BITS / window) * 2^window` points.
+    ///
+    /// Panics if `window` is not in `1..=16`.
+    pub fn with_window(base: &C, window: usize) -> Self {
+        assert!((1..=16).contains(&window));
+
+        let num_rows = (C::Scalar::NUM_BITS as usize + window - 1) / window;
+        let row_len = 1 << window;
+
+        let mut points = Vec::with_capacity(num_rows * row_len);
+        let mut row_base = base.to_curve();
+        for _ in 0..num_rows {
+            let mut acc = C::Curve::identity();
+            for _ in 0..row_len {
+                points.push(acc);
+                acc += row_base;
+            }
+            // acc = 2^window * row_base
+            row_base = acc;
+        }
+
+        let mut affine = vec![C::identity(); points.len()];
+        C::Curve::batch_normalize(&points, &mut affine);
+
+        let rows = affine.chunks(row_len).map(|row| row.to_vec()).collect();
+        FixedBaseTable { window, rows }
+    }
+
+    /// Returns the window width of the table.
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Computes `base * scalar` in constant time.
+    pub fn mul(&self, scalar: &C::Scalar) -> C::Curve {
+        let repr = scalar.to_repr();
+        let bytes = repr.as_ref();
+
+        let mut acc = C::Curve::identity();
+        for (i, row) in self.rows.iter().enumerate() {
+            let digit = get_window(bytes, i * self.window, self.window);
+            let mut term = C::identity();
+            for (j, point) in row.iter().enumerate() {
+                term.conditional_assign(point, (j as u32).ct_eq(&digit));
+            }
+            acc += term;
+        }
+        acc
+    }
+}
+
+/// Returns the `window` bits of the little-endian integer `bytes` starting at
+/// bit `offset`.
+fn get_window(bytes: &[u8], offset: usize, window: usize) -> u32 {
+    (0..window).fold(0, |acc, i| {
+        let bit = offset + i;
+        let byte = bytes.get(bit / 8).copied().unwrap_or(0);
+        acc | (u32::from((byte >> (bit % 8)) & 1) << i)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::FixedBaseTable;
+    use ff::Field;
+    use group::{Curve, Group};
+    use pasta_curves::arithmetic::CurveAffine;
+    use rand_core::OsRng;
+
+    fn run_fixed_base<C: CurveAffine>() {
+        let base = C::Curve::random(OsRng).to_affine();
+        for window in 1..=8 {
+            let table = FixedBaseTable::with_window(&base, window);
+            assert_eq!(table.window(), window);
+
+            let edge_cases = [C::Scalar::ZERO, C::Scalar::ONE, -C::Scalar::ONE];
+            for scalar in edge_cases
+                .into_iter()
+                .chain((0..10).map(|_| C::Scalar::random(OsRng)))
+            {
+                assert_eq!(table.mul(&scalar), base * scalar);
+            }
+        }
+
+        let table = FixedBaseTable::new(&C::identity());
+        let scalar = C::Scalar::random(OsRng);
+        assert_eq!(table.mul(&scalar), C::Curve::identity());
+    }
+
+    #[test]
+    fn test_fixed_base() {
+        run_fixed_base::<crate::bn256::G1Affine>();
+        run_fixed_base::<crate::bn256::G2Affine>();
+        run_fixed_base::<crate::grumpkin::G1Affine>();
+        run_fixed_base::<crate::secp256k1::Secp256k1Affine>();
+        run_fixed_base::<crate::secp256r1::Secp256r1Affine>();
+        run_fixed_base::<crate::secq256k1::Secq256k1Affine>();
+        run_fixed_base::<crate::pasta::EpAffine>();
+    }
+}
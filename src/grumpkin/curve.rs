@@ -8,7 +8,10 @@ use crate::group::Curve;
 use crate::group::{prime::PrimeCurveAffine, Group, GroupEncoding};
 use crate::grumpkin::Fq;
 use crate::grumpkin::Fr;
-use crate::hash_to_curve::svdw_hash_to_curve;
+use crate::hash_to_curve::{
+    svdw_encode_to_curve, svdw_hash_to_curve, svdw_hash_to_curve_with, EncodeToCurve,
+    ExpandMessage, HashToCurveWith,
+};
 use crate::{
     endo, impl_add_binop_specify_output, impl_binops_additive, impl_binops_additive_specify_output,
     impl_binops_multiplicative, impl_binops_multiplicative_mixed, impl_sub_binop_specify_output,
@@ -89,6 +92,20 @@ impl G1 {
     const SVDW_Z: Fq = Fq::ONE;
 }
 
+impl EncodeToCurve for G1 {
+    fn encode_to_curve<'a>(domain_prefix: &'a str) -> Box<dyn Fn(&[u8]) -> Self + 'a> {
+        svdw_encode_to_curve(Self::CURVE_ID, domain_prefix, G1::SVDW_Z)
+    }
+}
+
+impl HashToCurveWith for G1 {
+    fn hash_to_curve_with<'a, X: ExpandMessage + 'a>(
+        dst: &[u8],
+    ) -> Box<dyn Fn(&[u8]) -> Self + 'a> {
+        svdw_hash_to_curve_with::<G1, X, 64>(dst.to_vec(), G1::SVDW_Z)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::arithmetic::CurveEndo;
@@ -100,6 +117,12 @@ mod tests {
     #[test]
     fn test_hash_to_curve() {
         crate::tests::curve::hash_to_curve_test::<G1>();
+        crate::tests::curve::hash_to_curve_with_test::<G1>("SVDW");
+    }
+
+    #[test]
+    fn test_encode_to_curve() {
+        crate::tests::curve::encode_to_curve_test::<G1>();
     }
 
     #[test]
@@ -126,6 +149,7 @@ mod tests {
         for _ in 0..100000 {
             let k = Fr::random(OsRng);
             let (k1, k1_neg, k2, k2_neg) = G1::decompose_scalar(&k);
+            let (k1_neg, k2_neg) = (bool::from(k1_neg), bool::from(k2_neg));
             if k1_neg & k2_neg {
                 assert_eq!(k, -Fr::from_u128(k1) + Fr::ZETA * Fr::from_u128(k2))
             } else if k1_neg {
@@ -136,6 +160,13 @@ mod tests {
                 assert_eq!(k, Fr::from_u128(k1) - Fr::ZETA * Fr::from_u128(k2))
             }
         }
+
+        crate::tests::curve::endo_decomposition_test::<G1>();
+    }
+
+    #[test]
+    fn test_glv_mul() {
+        crate::tests::curve::glv_mul_test::<G1>();
     }
 
     #[test]
@@ -1,100 +1,415 @@
 #![allow(clippy::op_ref)]
 
+use core::marker::PhantomData;
+use digest::{core_api::BlockSizeUser, Digest, ExtendableOutput, Update};
 use ff::{Field, FromUniformBytes, PrimeField};
 use pasta_curves::arithmetic::CurveExt;
-use static_assertions::const_assert;
+use rand_core::RngCore;
+use sha2::Sha256;
 use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
 
 use crate::legendre::Legendre;
 
 /// Hashes over a message and writes the output to all of `buf`.
+///
+/// Every field element is built from `L` bytes, that is from `L / 64`
+/// big-endian chunks of 64 bytes, one per coordinate of the field over its
+/// prime subfield.
 /// Modified from https://github.com/zcash/pasta_curves/blob/7e3fc6a4919f6462a32b79dd226cb2587b7961eb/src/hashtocurve.rs#L11.
-fn hash_to_field<F: FromUniformBytes<64>>(
-    method: &str,
-    curve_id: &str,
-    domain_prefix: &str,
+fn legacy_hash_to_field<F: FromUniformBytes<L>, X: ExpandMessage, const L: usize>(
+    dst: &[u8],
     message: &[u8],
-    buf: &mut [F; 2],
+    buf: &mut [F],
 ) {
-    assert!(domain_prefix.len() < 256);
-    assert!((18 + method.len() + curve_id.len() + domain_prefix.len()) < 256);
-
     // Assume that the field size is 32 bytes and k is 256, where k is defined in
     // <https://www.ietf.org/archive/id/draft-irtf-cfrg-hash-to-curve-10.html#name-security-considerations-3>.
     const CHUNKLEN: usize = 64;
-    const_assert!(CHUNKLEN * 2 < 256);
-
-    // Input block size of BLAKE2b.
-    const R_IN_BYTES: usize = 128;
-
-    let personal = [0u8; 16];
-    let empty_hasher = blake2b_simd::Params::new()
-        .hash_length(CHUNKLEN)
-        .personal(&personal)
-        .to_state();
-
-    let b_0 = empty_hasher
-        .clone()
-        .update(&[0; R_IN_BYTES])
-        .update(message)
-        .update(&[0, (CHUNKLEN * 2) as u8, 0])
-        .update(domain_prefix.as_bytes())
-        .update(b"-")
-        .update(curve_id.as_bytes())
-        .update(b"_XMD:BLAKE2b_")
-        .update(method.as_bytes())
-        .update(b"_RO_")
-        .update(&[(18 + method.len() + curve_id.len() + domain_prefix.len()) as u8])
-        .finalize();
-
-    let b_1 = empty_hasher
-        .clone()
-        .update(b_0.as_array())
-        .update(&[1])
-        .update(domain_prefix.as_bytes())
-        .update(b"-")
-        .update(curve_id.as_bytes())
-        .update(b"_XMD:BLAKE2b_")
-        .update(method.as_bytes())
-        .update(b"_RO_")
-        .update(&[(18 + method.len() + curve_id.len() + domain_prefix.len()) as u8])
-        .finalize();
-
-    let b_2 = {
-        let mut empty_hasher = empty_hasher;
-        for (l, r) in b_0.as_array().iter().zip(b_1.as_array().iter()) {
-            empty_hasher.update(&[*l ^ *r]);
-        }
-        empty_hasher
-            .update(&[2])
-            .update(domain_prefix.as_bytes())
-            .update(b"-")
-            .update(curve_id.as_bytes())
-            .update(b"_XMD:BLAKE2b_")
-            .update(method.as_bytes())
-            .update(b"_RO_")
-            .update(&[(18 + method.len() + curve_id.len() + domain_prefix.len()) as u8])
-            .finalize()
-    };
+    assert!(L % CHUNKLEN == 0 && L > 0);
 
-    for (big, buf) in [b_1, b_2].iter().zip(buf.iter_mut()) {
-        let mut little = [0u8; CHUNKLEN];
-        little.copy_from_slice(big.as_array());
-        little.reverse();
+    let uniform_bytes = X::expand_message(message, dst, L * buf.len());
+    for (big, buf) in uniform_bytes.chunks(L).zip(buf.iter_mut()) {
+        let mut little = [0u8; L];
+        for (little, big) in little.chunks_mut(CHUNKLEN).zip(big.chunks(CHUNKLEN)) {
+            little.copy_from_slice(big);
+            little.reverse();
+        }
         *buf = F::from_uniform_bytes(&little);
     }
 }
 
+/// Domain separation tag of the `hash_to_curve` and `encode_to_curve`
+/// implementations of the curves,
+/// `{domain_prefix}-{curve_id}_{expander}_{method}_{encoding}_`.
+pub(crate) fn legacy_dst(
+    domain_prefix: &str,
+    curve_id: &str,
+    expander: &str,
+    method: &str,
+    encoding: &str,
+) -> Vec<u8> {
+    let dst = format!("{domain_prefix}-{curve_id}_{expander}_{method}_{encoding}_");
+    assert!(dst.len() < 256);
+    dst.into_bytes()
+}
+
+/// Expansion of a message into a uniformly random byte string, as specified in
+/// https://datatracker.ietf.org/doc/html/rfc9380#section-5.3.
+pub trait ExpandMessage {
+    /// Expands `message` into `len_in_bytes` bytes under the domain separation
+    /// tag `dst`.
+    fn expand_message(message: &[u8], dst: &[u8], len_in_bytes: usize) -> Vec<u8>;
+}
+
+/// `expand_message_xmd` over the hash function `H`, see
+/// https://datatracker.ietf.org/doc/html/rfc9380#section-5.3.1.
+///
+/// Tags longer than 255 bytes are hashed as described in
+/// https://datatracker.ietf.org/doc/html/rfc9380#section-5.3.3.
+///
+/// # Panics
+///
+/// Panics if `len_in_bytes` exceeds `255` times the output size of `H`, or
+/// `65535`.
+#[derive(Clone, Copy, Debug)]
+pub struct ExpandMsgXmd<H>(PhantomData<H>);
+
+impl<H: Digest + BlockSizeUser> ExpandMessage for ExpandMsgXmd<H> {
+    fn expand_message(message: &[u8], dst: &[u8], len_in_bytes: usize) -> Vec<u8> {
+        let b_in_bytes = <H as Digest>::output_size();
+        let r_in_bytes = H::block_size();
+        let ell = (len_in_bytes + b_in_bytes - 1) / b_in_bytes;
+        assert!(ell <= 255 && len_in_bytes <= 65535);
+
+        let long_dst;
+        let dst = if dst.len() > 255 {
+            long_dst = H::new()
+                .chain_update(b"H2C-OVERSIZE-DST-")
+                .chain_update(dst)
+                .finalize();
+            &long_dst[..]
+        } else {
+            dst
+        };
+        // DST_prime = DST || I2OSP(len(DST), 1)
+        let dst_len = [dst.len() as u8];
+
+        // b_0 = H(Z_pad || msg || l_i_b_str || I2OSP(0, 1) || DST_prime)
+        let b_0 = H::new()
+            .chain_update(vec![0; r_in_bytes])
+            .chain_update(message)
+            .chain_update((len_in_bytes as u16).to_be_bytes())
+            .chain_update([0])
+            .chain_update(dst)
+            .chain_update(dst_len)
+            .finalize();
+
+        // b_1 = H(b_0 || I2OSP(1, 1) || DST_prime)
+        let mut b_i = H::new()
+            .chain_update(&b_0)
+            .chain_update([1])
+            .chain_update(dst)
+            .chain_update(dst_len)
+            .finalize();
+
+        let mut uniform_bytes = Vec::with_capacity(ell * b_in_bytes);
+        uniform_bytes.extend_from_slice(&b_i);
+        for i in 2..=ell {
+            // b_i = H(strxor(b_0, b_(i - 1)) || I2OSP(i, 1) || DST_prime)
+            let xored: Vec<u8> = b_0.iter().zip(b_i.iter()).map(|(l, r)| l ^ r).collect();
+            b_i = H::new()
+                .chain_update(xored)
+                .chain_update([i as u8])
+                .chain_update(dst)
+                .chain_update(dst_len)
+                .finalize();
+            uniform_bytes.extend_from_slice(&b_i);
+        }
+        uniform_bytes.truncate(len_in_bytes);
+        uniform_bytes
+    }
+}
+
+/// `expand_message_xmd` over BLAKE2b-512, the expander of the
+/// `hash_to_curve` implementations of [`CurveExt`].
+///
+/// # Panics
+///
+/// Panics if `len_in_bytes` exceeds `255 * 64`.
+#[derive(Clone, Copy, Debug)]
+pub struct ExpandMsgXmdBlake2b;
+
+impl ExpandMessage for ExpandMsgXmdBlake2b {
+    fn expand_message(message: &[u8], dst: &[u8], len_in_bytes: usize) -> Vec<u8> {
+        const B_IN_BYTES: usize = 64;
+        const R_IN_BYTES: usize = 128;
+        let ell = (len_in_bytes + B_IN_BYTES - 1) / B_IN_BYTES;
+        assert!(ell <= 255 && len_in_bytes <= 65535);
+
+        let personal = [0u8; 16];
+        let empty_hasher = blake2b_simd::Params::new()
+            .hash_length(B_IN_BYTES)
+            .personal(&personal)
+            .to_state();
+
+        let long_dst;
+        let dst = if dst.len() > 255 {
+            long_dst = empty_hasher
+                .clone()
+                .update(b"H2C-OVERSIZE-DST-")
+                .update(dst)
+                .finalize();
+            long_dst.as_bytes()
+        } else {
+            dst
+        };
+        let dst_prime = |hasher: &mut blake2b_simd::State| {
+            hasher.update(dst).update(&[dst.len() as u8]);
+        };
+
+        let b_0 = {
+            let mut hasher = empty_hasher.clone();
+            hasher
+                .update(&[0; R_IN_BYTES])
+                .update(message)
+                .update(&(len_in_bytes as u16).to_be_bytes())
+                .update(&[0]);
+            dst_prime(&mut hasher);
+            hasher.finalize()
+        };
+
+        // b_1 = H(b_0 || 1 || DST) and b_i = H((b_0 xor b_(i-1)) || i || DST).
+        let mut uniform_bytes = Vec::with_capacity(ell * B_IN_BYTES);
+        let mut b_i = {
+            let mut hasher = empty_hasher.clone();
+            hasher.update(b_0.as_array()).update(&[1]);
+            dst_prime(&mut hasher);
+            hasher.finalize()
+        };
+        uniform_bytes.extend_from_slice(b_i.as_array());
+        for i in 2..=ell {
+            let mut hasher = empty_hasher.clone();
+            for (l, r) in b_0.as_array().iter().zip(b_i.as_array().iter()) {
+                hasher.update(&[*l ^ *r]);
+            }
+            hasher.update(&[i as u8]);
+            dst_prime(&mut hasher);
+            b_i = hasher.finalize();
+            uniform_bytes.extend_from_slice(b_i.as_array());
+        }
+        uniform_bytes.truncate(len_in_bytes);
+        uniform_bytes
+    }
+}
+
+/// `expand_message_xof` over the extendable-output function `H` with a
+/// target security level of `K` bits, see
+/// https://datatracker.ietf.org/doc/html/rfc9380#section-5.3.2.
+///
+/// `K` is 128 for SHAKE128 and 256 for SHAKE256. It sets the length of the
+/// hashed tag when the tag is longer than 255 bytes, see
+/// https://datatracker.ietf.org/doc/html/rfc9380#section-5.3.3.
+///
+/// # Panics
+///
+/// Panics if `len_in_bytes` exceeds `65535`.
+#[derive(Clone, Copy, Debug)]
+pub struct ExpandMsgXof<H, const K: usize>(PhantomData<H>);
+
+impl<H: Default + Update + ExtendableOutput, const K: usize> ExpandMessage for ExpandMsgXof<H, K> {
+    fn expand_message(message: &[u8], dst: &[u8], len_in_bytes: usize) -> Vec<u8> {
+        assert!(len_in_bytes <= 65535);
+
+        let long_dst;
+        let dst = if dst.len() > 255 {
+            let mut hasher = H::default();
+            hasher.update(b"H2C-OVERSIZE-DST-");
+            hasher.update(dst);
+            long_dst = hasher.finalize_boxed((2 * K + 7) / 8);
+            &long_dst[..]
+        } else {
+            dst
+        };
+
+        // uniform_bytes = H(msg || I2OSP(len_in_bytes, 2) || DST_prime, len_in_bytes)
+        let mut hasher = H::default();
+        hasher.update(message);
+        hasher.update(&(len_in_bytes as u16).to_be_bytes());
+        hasher.update(dst);
+        hasher.update(&[dst.len() as u8]);
+        hasher.finalize_boxed(len_in_bytes).into_vec()
+    }
+}
+
+/// Hashes `message` to `count` field elements under the domain separation
+/// tag `dst`, as specified in
+/// https://datatracker.ietf.org/doc/html/rfc9380#section-5.2, with
+/// `expand_message_xmd` over SHA-256.
+///
+/// `F` may be the base field of a curve as well as its scalar field, e.g. to
+/// derive Fiat-Shamir challenges. Every element is reduced from
+/// `L = ceil((NUM_BITS + 128) / 8)` bytes, for `128` bits of security.
+///
+/// # Panics
+///
+/// Panics if `L` exceeds `64`, or if `L * count` exceeds the `255 * 32`
+/// bytes that `expand_message_xmd` over SHA-256 can produce, i.e. if `count`
+/// exceeds `170` for fields of at most 256 bits (`L = 48`).
+pub fn hash_to_field<F: FromUniformBytes<64>>(dst: &[u8], message: &[u8], count: usize) -> Vec<F> {
+    hash_to_field_with::<F, ExpandMsgXmd<Sha256>>(dst, message, count)
+}
+
+/// Same as [`hash_to_field`], expanding the message with `X`.
+///
+/// # Panics
+///
+/// Panics if `L` exceeds `64`, or if `X` cannot expand the message to
+/// `L * count` bytes.
+pub fn hash_to_field_with<F, X>(dst: &[u8], message: &[u8], count: usize) -> Vec<F>
+where
+    F: FromUniformBytes<64>,
+    X: ExpandMessage,
+{
+    let l = (F::NUM_BITS as usize + 128 + 7) / 8;
+    assert!(l <= 64);
+
+    let uniform_bytes = X::expand_message(message, dst, l * count);
+    uniform_bytes
+        .chunks(l)
+        .map(|big| {
+            let mut little = [0u8; 64];
+            little[..l].copy_from_slice(big);
+            little[..l].reverse();
+            F::from_uniform_bytes(&little)
+        })
+        .collect()
+}
+
+/// Hashes `message` to a single element of `F`, see [`hash_to_field`].
+pub fn hash_to_scalar<F: FromUniformBytes<64>>(dst: &[u8], message: &[u8]) -> F {
+    hash_to_field(dst, message, 1)[0]
+}
+
+/// A hash-to-curve suite, as specified in
+/// https://datatracker.ietf.org/doc/html/rfc9380#section-8.
+pub trait Suite {
+    /// The curve hashed to.
+    type Curve: CurveExt;
+    /// The expander used by `hash_to_field`.
+    type Expander: ExpandMessage;
+    /// Identifier of the random oracle encoding, such as
+    /// `P256_XMD:SHA-256_SSWU_RO_`. Applications should append it to their
+    /// own tag to build the domain separation tag of `hash_to_curve`.
+    const RO_ID: &'static str;
+    /// Identifier of the nonuniform encoding, such as
+    /// `P256_XMD:SHA-256_SSWU_NU_`, for the tag of `encode_to_curve`.
+    const NU_ID: &'static str;
+
+    /// Deterministically maps a field element to the curve, in constant
+    /// time. The result is only `None` if the constants of the map are not
+    /// valid for the curve.
+    fn map_to_curve(u: <Self::Curve as CurveExt>::Base) -> CtOption<Self::Curve>;
+
+    /// Maps a point of the curve to the prime order subgroup. The default
+    /// is the identity, for curves of prime order.
+    fn clear_cofactor(p: Self::Curve) -> Self::Curve {
+        p
+    }
+
+    /// `hash_to_field` of https://datatracker.ietf.org/doc/html/rfc9380#section-5.2
+    /// with the expander of the suite. The default, [`hash_to_field_with`],
+    /// is only valid for curves over prime fields.
+    fn hash_to_field(
+        message: &[u8],
+        dst: &[u8],
+        count: usize,
+    ) -> Vec<<Self::Curve as CurveExt>::Base>
+    where
+        <Self::Curve as CurveExt>::Base: FromUniformBytes<64>,
+    {
+        hash_to_field_with::<_, Self::Expander>(dst, message, count)
+    }
+
+    /// `hash_to_curve` of https://datatracker.ietf.org/doc/html/rfc9380#section-3
+    /// under the domain separation tag `dst`.
+    fn hash_to_curve(message: &[u8], dst: &[u8]) -> Self::Curve
+    where
+        <Self::Curve as CurveExt>::Base: FromUniformBytes<64>,
+    {
+        let us = Self::hash_to_field(message, dst, 2);
+
+        let q0 = Self::map_to_curve(us[0]);
+        let q1 = Self::map_to_curve(us[1]);
+        Self::clear_cofactor(unwrap_or_identity(q0.and_then(|q0| q1.map(|q1| q0 + q1))))
+    }
+
+    /// `encode_to_curve` of https://datatracker.ietf.org/doc/html/rfc9380#section-3
+    /// under the domain separation tag `dst`. It only maps one field element
+    /// and its output is not uniformly distributed.
+    fn encode_to_curve(message: &[u8], dst: &[u8]) -> Self::Curve
+    where
+        <Self::Curve as CurveExt>::Base: FromUniformBytes<64>,
+    {
+        let u = Self::hash_to_field(message, dst, 1);
+
+        Self::clear_cofactor(unwrap_or_identity(Self::map_to_curve(u[0])))
+    }
+}
+
+/// Nonuniform encoding of messages to a curve, the counterpart of
+/// [`CurveExt::hash_to_curve`] with the `encode_to_curve` construction of
+/// https://datatracker.ietf.org/doc/html/rfc9380#section-3.
+pub trait EncodeToCurve: CurveExt {
+    /// Returns an encoder of messages to the curve, with the domain
+    /// separation tag `{domain_prefix}-{CURVE_ID}_XMD:BLAKE2b_{method}_NU_`.
+    ///
+    /// It maps a single field element, so it costs about half of
+    /// [`CurveExt::hash_to_curve`], but its output is distinguishable from a
+    /// uniformly random point.
+    #[allow(clippy::type_complexity)]
+    fn encode_to_curve<'a>(domain_prefix: &'a str) -> Box<dyn Fn(&[u8]) -> Self + 'a>;
+}
+
+/// [`CurveExt::hash_to_curve`] instantiated with any [`ExpandMessage`], such
+/// as [`ExpandMsgXof`], in place of BLAKE2b.
+///
+/// The field elements, the map to the curve and the cofactor clearing are
+/// those of `hash_to_curve`: with [`ExpandMsgXmdBlake2b`] and the tag
+/// `{domain_prefix}-{CURVE_ID}_XMD:BLAKE2b_{method}_RO_`, both agree.
+pub trait HashToCurveWith: CurveExt {
+    /// Returns a hasher of messages to the curve, expanding them with `X`
+    /// under the domain separation tag `dst`.
+    #[allow(clippy::type_complexity)]
+    fn hash_to_curve_with<'a, X: ExpandMessage + 'a>(dst: &[u8])
+        -> Box<dyn Fn(&[u8]) -> Self + 'a>;
+}
+
+/// Resolves the output of a map to the curve. The maps are total, so `p` is
+/// only `None` if their constants are invalid for the curve, which the tests
+/// of every curve rule out; the identity is returned rather than panicking
+/// on such a bug.
+fn unwrap_or_identity<C: CurveExt>(p: CtOption<C>) -> C {
+    debug_assert!(bool::from(p.is_some()));
+    p.unwrap_or(C::identity())
+}
+
 // Implementation of <https://datatracker.ietf.org/doc/html/rfc9380#name-simplified-swu-method>
+//
+// The map is total and constant time. It returns `None` only if `z` is not a
+// valid constant for the curve.
 #[allow(clippy::too_many_arguments)]
-pub(crate) fn simple_svdw_map_to_curve<C>(u: C::Base, z: C::Base) -> C
+pub(crate) fn simple_svdw_map_to_curve<C>(u: C::Base, z: C::Base) -> CtOption<C>
 where
     C: CurveExt,
 {
-    let zero = C::Base::ZERO;
-    let one = C::Base::ONE;
-    let a = C::a();
-    let b = C::b();
+    let (x, y) = simple_svdw_map_to_coordinates(u, C::a(), C::b(), z);
+    C::new_jacobian(x, y, C::Base::ONE)
+}
+
+/// Simplified SWU map to the curve `y^2 = x^3 + a * x + b`, returning the
+/// affine coordinates of the image of `u`. Both `a` and `b` must be non-zero.
+fn simple_svdw_map_to_coordinates<F: PrimeField>(u: F, a: F, b: F, z: F) -> (F, F) {
+    let zero = F::ZERO;
+    let one = F::ONE;
 
     //1.  tv1 = u^2
     let tv1 = u.square();
@@ -110,7 +425,7 @@ where
     let tv3 = b * tv3;
     //7.  tv4 = CMOV(Z, -tv2, tv2 != 0) # tv4 = z if tv2 is 0 else tv4 = -tv2
     let tv2_is_not_zero = !tv2.ct_eq(&zero);
-    let tv4 = C::Base::conditional_select(&z, &-tv2, tv2_is_not_zero);
+    let tv4 = F::conditional_select(&z, &-tv2, tv2_is_not_zero);
     //8.  tv4 = A * tv4
     let tv4 = a * tv4;
     //9.  tv2 = tv3^2
@@ -138,17 +453,77 @@ where
     //20.   y = y * y1
     let y = y * y1;
     //21.   x = CMOV(x, tv3, is_gx1_square)
-    let x = C::Base::conditional_select(&x, &tv3, is_gx1_square);
+    let x = F::conditional_select(&x, &tv3, is_gx1_square);
     //22.   y = CMOV(y, y1, is_gx1_square)
-    let y = C::Base::conditional_select(&y, &y1, is_gx1_square);
+    let y = F::conditional_select(&y, &y1, is_gx1_square);
     //23.  e1 = sgn0(u) == sgn0(y)
     let e1 = u.is_odd().ct_eq(&y.is_odd());
     //24.   y = CMOV(-y, y, e1) # Select correct sign of y
-    let y = C::Base::conditional_select(&-y, &y, e1);
-    //25.   x = x / tv4
-    let x = x * tv4.invert().unwrap();
+    let y = F::conditional_select(&-y, &y, e1);
+    //25.   x = x / tv4 # tv4 != 0 since A != 0 and Z != 0
+    let x = x * tv4.invert().unwrap_or(zero);
     //26. return (x, y)
-    C::new_jacobian(x, y, one).unwrap()
+    (x, y)
+}
+
+/// A curve `E': y^2 = x^3 + A' * x + B'` with `A' * B' != 0` and the rational
+/// map of an isogeny from `E'` to a curve with `A * B = 0`, used to apply the
+/// simplified SWU map to the latter, see
+/// https://datatracker.ietf.org/doc/html/rfc9380#section-6.6.3.
+///
+/// The map is `(x, y) -> (x_num(x) / x_den(x), y * y_num(x) / y_den(x))`. The
+/// coefficients of every polynomial are listed from the constant term up, and
+/// the leading coefficient of both denominators, which is `1`, is omitted.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Isogeny<F: 'static> {
+    pub(crate) a: F,
+    pub(crate) b: F,
+    pub(crate) x_num: &'static [F],
+    pub(crate) x_den: &'static [F],
+    pub(crate) y_num: &'static [F],
+    pub(crate) y_den: &'static [F],
+}
+
+impl<F: Field> Isogeny<F> {
+    /// Evaluates the isogeny at the affine point `(x, y)` of `E'`. The
+    /// exceptional inputs, where a denominator vanishes, are mapped to the
+    /// identity. The result is `None` if `(x, y)` is not on `E'`.
+    pub(crate) fn map<C: CurveExt<Base = F>>(&self, x: F, y: F) -> CtOption<C> {
+        // Horner evaluation of a polynomial, with an implicit leading `1`
+        // when `monic` is set.
+        let eval = |coeffs: &[F], monic: bool| {
+            let init = if monic { F::ONE } else { F::ZERO };
+            coeffs.iter().rev().fold(init, |acc, coeff| acc * x + coeff)
+        };
+        let x_num = eval(self.x_num, false);
+        let x_den = eval(self.x_den, true);
+        let y_num = eval(self.y_num, false);
+        let y_den = eval(self.y_den, true);
+
+        // One inversion for both denominators.
+        let den = x_den * y_den;
+        let is_exceptional = den.is_zero();
+        let den_inv = den.invert().unwrap_or(F::ZERO);
+        let x = x_num * y_den * den_inv;
+        let y = y * y_num * x_den * den_inv;
+        let z = F::conditional_select(&F::ONE, &F::ZERO, is_exceptional);
+        C::new_jacobian(x, y, z)
+    }
+}
+
+/// Simplified SWU map to the isogenous curve of `iso`, followed by the
+/// isogeny back to `C`, see
+/// https://datatracker.ietf.org/doc/html/rfc9380#section-6.6.3.
+pub(crate) fn simple_svdw_isogeny_map_to_curve<C>(
+    u: C::Base,
+    z: C::Base,
+    iso: &Isogeny<C::Base>,
+) -> CtOption<C>
+where
+    C: CurveExt,
+{
+    let (x, y) = simple_svdw_map_to_coordinates(u, iso.a, iso.b, z);
+    iso.map(x, y)
 }
 
 #[allow(clippy::type_complexity)]
@@ -160,19 +535,121 @@ pub(crate) fn simple_svdw_hash_to_curve<'a, C>(
 where
     C: CurveExt,
     C::Base: FromUniformBytes<64>,
+{
+    let dst = legacy_dst(domain_prefix, curve_id, "XMD:BLAKE2b", "SSWU", "RO");
+    simple_svdw_hash_to_curve_with::<C, ExpandMsgXmdBlake2b>(dst, z)
+}
+
+/// Same as [`simple_svdw_hash_to_curve`], expanding the message with `X`
+/// under the domain separation tag `dst`.
+#[allow(clippy::type_complexity)]
+pub(crate) fn simple_svdw_hash_to_curve_with<'a, C, X>(
+    dst: Vec<u8>,
+    z: C::Base,
+) -> Box<dyn Fn(&[u8]) -> C + 'a>
+where
+    C: CurveExt,
+    C::Base: FromUniformBytes<64>,
+    X: ExpandMessage,
+{
+    Box::new(move |message| {
+        let mut us = [C::Base::ZERO; 2];
+        legacy_hash_to_field::<_, X, 64>(&dst, message, &mut us);
+
+        let [q0, q1]: [CtOption<C>; 2] = us.map(|u| simple_svdw_map_to_curve(u, z));
+
+        let r = unwrap_or_identity(q0.and_then(|q0| q1.map(|q1| q0 + q1)));
+        debug_assert!(bool::from(r.is_on_curve()));
+        r
+    })
+}
+
+#[allow(clippy::type_complexity)]
+pub(crate) fn simple_svdw_encode_to_curve<'a, C>(
+    curve_id: &'static str,
+    domain_prefix: &'a str,
+    z: C::Base,
+) -> Box<dyn Fn(&[u8]) -> C + 'a>
+where
+    C: CurveExt,
+    C::Base: FromUniformBytes<64>,
+{
+    let dst = legacy_dst(domain_prefix, curve_id, "XMD:BLAKE2b", "SSWU", "NU");
+    Box::new(move |message| {
+        let mut u = [C::Base::ZERO];
+        legacy_hash_to_field::<_, ExpandMsgXmdBlake2b, 64>(&dst, message, &mut u);
+        unwrap_or_identity(simple_svdw_map_to_curve(u[0], z))
+    })
+}
+
+/// Same as [`simple_svdw_hash_to_curve`], for a curve with `A * B = 0`
+/// through the isogeny `iso`, see
+/// https://datatracker.ietf.org/doc/html/rfc9380#section-6.6.3.
+#[allow(clippy::type_complexity)]
+pub(crate) fn simple_svdw_isogeny_hash_to_curve<'a, C>(
+    curve_id: &'static str,
+    domain_prefix: &'a str,
+    z: C::Base,
+    iso: Isogeny<C::Base>,
+) -> Box<dyn Fn(&[u8]) -> C + 'a>
+where
+    C: CurveExt,
+    C::Base: FromUniformBytes<64>,
+{
+    let dst = legacy_dst(domain_prefix, curve_id, "XMD:BLAKE2b", "SSWU", "RO");
+    simple_svdw_isogeny_hash_to_curve_with::<C, ExpandMsgXmdBlake2b>(dst, z, iso)
+}
+
+/// Same as [`simple_svdw_isogeny_hash_to_curve`], expanding the message with
+/// `X` under the domain separation tag `dst`.
+#[allow(clippy::type_complexity)]
+pub(crate) fn simple_svdw_isogeny_hash_to_curve_with<'a, C, X>(
+    dst: Vec<u8>,
+    z: C::Base,
+    iso: Isogeny<C::Base>,
+) -> Box<dyn Fn(&[u8]) -> C + 'a>
+where
+    C: CurveExt,
+    C::Base: FromUniformBytes<64>,
+    X: ExpandMessage,
 {
     Box::new(move |message| {
         let mut us = [C::Base::ZERO; 2];
-        hash_to_field("SSWU", curve_id, domain_prefix, message, &mut us);
+        legacy_hash_to_field::<_, X, 64>(&dst, message, &mut us);
 
-        let [q0, q1]: [C; 2] = us.map(|u| simple_svdw_map_to_curve(u, z));
+        let [q0, q1]: [CtOption<C>; 2] = us.map(|u| simple_svdw_isogeny_map_to_curve(u, z, &iso));
 
-        let r = q0 + &q1;
+        let r = unwrap_or_identity(q0.and_then(|q0| q1.map(|q1| q0 + q1)));
         debug_assert!(bool::from(r.is_on_curve()));
         r
     })
 }
 
+/// Same as [`simple_svdw_encode_to_curve`], for a curve with `A * B = 0`
+/// through the isogeny `iso`.
+#[allow(clippy::type_complexity)]
+pub(crate) fn simple_svdw_isogeny_encode_to_curve<'a, C>(
+    curve_id: &'static str,
+    domain_prefix: &'a str,
+    z: C::Base,
+    iso: Isogeny<C::Base>,
+) -> Box<dyn Fn(&[u8]) -> C + 'a>
+where
+    C: CurveExt,
+    C::Base: FromUniformBytes<64>,
+{
+    let dst = legacy_dst(domain_prefix, curve_id, "XMD:BLAKE2b", "SSWU", "NU");
+    Box::new(move |message| {
+        let mut u = [C::Base::ZERO];
+        legacy_hash_to_field::<_, ExpandMsgXmdBlake2b, 64>(&dst, message, &mut u);
+        unwrap_or_identity(simple_svdw_isogeny_map_to_curve(u[0], z, &iso))
+    })
+}
+
+// Implementation of <https://datatracker.ietf.org/doc/html/rfc9380#name-shallue-van-de-woestijne-met>
+//
+// The map is total and constant time. It returns `None` only if the
+// constants are not valid for the curve.
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn svdw_map_to_curve<C>(
     u: C::Base,
@@ -181,7 +658,7 @@ pub(crate) fn svdw_map_to_curve<C>(
     c3: C::Base,
     c4: C::Base,
     z: C::Base,
-) -> C
+) -> CtOption<C>
 where
     C: CurveExt,
     C::Base: Legendre,
@@ -255,13 +732,14 @@ where
     // 32. gx = gx + B
     let gx = gx + b;
     // 33. y = sqrt(gx)
-    let y = gx.sqrt().unwrap();
-    // 34. e3 = sgn0(u) == sgn0(y)
-    let e3 = u.is_odd().ct_eq(&y.is_odd());
-    // 35. y = CMOV(-y, y, e3)    # Select correct sign of y
-    let y = C::Base::conditional_select(&-y, &y, e3);
-    // 36. return (x, y)
-    C::new_jacobian(x, y, one).unwrap()
+    gx.sqrt().and_then(|y| {
+        // 34. e3 = sgn0(u) == sgn0(y)
+        let e3 = u.is_odd().ct_eq(&y.is_odd());
+        // 35. y = CMOV(-y, y, e3)    # Select correct sign of y
+        let y = C::Base::conditional_select(&-y, &y, e3);
+        // 36. return (x, y)
+        C::new_jacobian(x, y, one)
+    })
 }
 
 // Implement https://datatracker.ietf.org/doc/html/rfc9380#name-sqrt_ratio-for-any-field
@@ -281,6 +759,9 @@ fn sqrt_ratio<F: PrimeField>(num: &F, div: &F, z: &F) -> (Choice, F) {
     // only one of them is square. We can therefore choose the square root to return
     // based on whether a is square, but for the boolean output we need to handle the
     // num != 0 && div == 0 case specifically.
+    //
+    // If z is a square, neither root may exist; zero is returned then, and the
+    // caller ends up off the curve instead of panicking.
 
     let a = div.invert().unwrap_or(F::ZERO) * num;
     let b = a * z;
@@ -290,14 +771,10 @@ fn sqrt_ratio<F: PrimeField>(num: &F, div: &F, z: &F) -> (Choice, F) {
     let num_is_zero = num.is_zero();
     let div_is_zero = div.is_zero();
     let is_square = sqrt_a.is_some();
-    let is_nonsquare = sqrt_b.is_some();
-    assert!(bool::from(
-        num_is_zero | div_is_zero | (is_square ^ is_nonsquare)
-    ));
 
     (
         is_square & (num_is_zero | !div_is_zero),
-        CtOption::conditional_select(&sqrt_b, &sqrt_a, is_square).unwrap(),
+        CtOption::conditional_select(&sqrt_b, &sqrt_a, is_square).unwrap_or(F::ZERO),
     )
 }
 
@@ -311,21 +788,75 @@ pub(crate) fn svdw_hash_to_curve<'a, C>(
 where
     C: CurveExt,
     C::Base: FromUniformBytes<64> + Legendre,
+{
+    let dst = legacy_dst(domain_prefix, curve_id, "XMD:BLAKE2b", "SVDW", "RO");
+    svdw_hash_to_curve_with::<C, ExpandMsgXmdBlake2b, 64>(dst, z)
+}
+
+/// Same as [`svdw_hash_to_curve`], expanding the message with `X` under the
+/// domain separation tag `dst` and building every field element from `L`
+/// bytes. Extension fields of degree `m` use `L = 64 * m`.
+///
+/// The result is not multiplied by the cofactor.
+#[allow(clippy::type_complexity)]
+pub(crate) fn svdw_hash_to_curve_with<'a, C, X, const L: usize>(
+    dst: Vec<u8>,
+    z: C::Base,
+) -> Box<dyn Fn(&[u8]) -> C + 'a>
+where
+    C: CurveExt,
+    C::Base: FromUniformBytes<L> + Legendre,
+    X: ExpandMessage,
 {
     let [c1, c2, c3, c4] = svdw_precomputed_constants::<C>(z);
 
     Box::new(move |message| {
         let mut us = [C::Base::ZERO; 2];
-        hash_to_field("SVDW", curve_id, domain_prefix, message, &mut us);
+        legacy_hash_to_field::<_, X, L>(&dst, message, &mut us);
 
-        let [q0, q1]: [C; 2] = us.map(|u| svdw_map_to_curve(u, c1, c2, c3, c4, z));
+        let [q0, q1]: [CtOption<C>; 2] = us.map(|u| svdw_map_to_curve(u, c1, c2, c3, c4, z));
 
-        let r = q0 + &q1;
+        let r = unwrap_or_identity(q0.and_then(|q0| q1.map(|q1| q0 + q1)));
         debug_assert!(bool::from(r.is_on_curve()));
         r
     })
 }
 
+#[allow(clippy::type_complexity)]
+pub(crate) fn svdw_encode_to_curve<'a, C>(
+    curve_id: &'static str,
+    domain_prefix: &'a str,
+    z: C::Base,
+) -> Box<dyn Fn(&[u8]) -> C + 'a>
+where
+    C: CurveExt,
+    C::Base: FromUniformBytes<64> + Legendre,
+{
+    svdw_encode_to_curve_with_len::<C, 64>(curve_id, domain_prefix, z)
+}
+
+/// Same as [`svdw_encode_to_curve`], building the field element from `L`
+/// bytes. The result is not multiplied by the cofactor.
+#[allow(clippy::type_complexity)]
+pub(crate) fn svdw_encode_to_curve_with_len<'a, C, const L: usize>(
+    curve_id: &'static str,
+    domain_prefix: &'a str,
+    z: C::Base,
+) -> Box<dyn Fn(&[u8]) -> C + 'a>
+where
+    C: CurveExt,
+    C::Base: FromUniformBytes<L> + Legendre,
+{
+    let dst = legacy_dst(domain_prefix, curve_id, "XMD:BLAKE2b", "SVDW", "NU");
+    let [c1, c2, c3, c4] = svdw_precomputed_constants::<C>(z);
+
+    Box::new(move |message| {
+        let mut u = [C::Base::ZERO];
+        legacy_hash_to_field::<_, ExpandMsgXmdBlake2b, L>(&dst, message, &mut u);
+        unwrap_or_identity(svdw_map_to_curve(u[0], c1, c2, c3, c4, z))
+    })
+}
+
 pub(crate) fn svdw_precomputed_constants<C: CurveExt>(z: C::Base) -> [C::Base; 4] {
     let a = C::a();
     let b = C::b();
@@ -348,3 +879,398 @@ pub(crate) fn svdw_precomputed_constants<C: CurveExt>(z: C::Base) -> [C::Base; 4
 
     [c1, c2, c3, c4]
 }
+
+/// Returns the candidate preimages of `p` under [`svdw_map_to_curve`], the
+/// roots of the equation of each of its three branches. The valid candidates
+/// are distinct and map to `p`; they are all the preimages of `p` but the at
+/// most four `u` with `c1 * u^2 = ±1`, where `inv0` is applied to zero.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn svdw_map_to_curve_inverse<C>(
+    p: &C,
+    c1: C::Base,
+    c2: C::Base,
+    c3: C::Base,
+    c4: C::Base,
+    z: C::Base,
+) -> [CtOption<C::Base>; 8]
+where
+    C: CurveExt,
+    C::Base: Legendre,
+{
+    let zero = C::Base::ZERO;
+    let one = C::Base::ONE;
+    let (x, is_finite) = {
+        // Jacobian coordinates, x = X / Z^2.
+        let (x, _, z) = p.jacobian_coordinates();
+        let z_inv = z.invert();
+        (x * z_inv.unwrap_or(zero).square(), z_inv.is_some())
+    };
+
+    // x = x1 = c2 - u * c3 / (1 + c1 * u^2), that is
+    // c1 * w * u^2 - c3 * u + w = 0 with w = c2 - x.
+    let w = c2 - x;
+    let den = (c1 * w).double().invert().unwrap_or(zero);
+    let root = (c3.square() - (c1 * w.square()).double().double()).sqrt();
+    let r = root.unwrap_or(zero);
+    let u1 = [(c3 + r) * den, (c3 - r) * den];
+    // x = x2 = c2 + u * c3 / (1 + c1 * u^2) has the opposite roots.
+    let u2 = u1.map(|u| -u);
+
+    // x = x3 = Z + c4 * s^2 with s = (1 + c1 * u^2) / (1 - c1 * u^2), that
+    // is c1 * u^2 = (s - 1) / (s + 1).
+    let s = ((x - z) * c4.invert().unwrap_or(zero)).sqrt();
+    let s = s.unwrap_or(zero);
+    let u3 = [s, -s].map(|s| {
+        let t = (s - one) * (s + one).invert().unwrap_or(zero);
+        let u = (t * c1.invert().unwrap_or(zero)).sqrt().unwrap_or(zero);
+        [u, -u]
+    });
+
+    let candidates = [
+        u1[0], u1[1], u2[0], u2[1], u3[0][0], u3[0][1], u3[1][0], u3[1][1],
+    ];
+    let mut preimages = candidates.map(|u| {
+        let q = svdw_map_to_curve::<C>(u, c1, c2, c3, c4, z);
+        let is_preimage = q.is_some() & q.unwrap_or(C::identity()).ct_eq(p);
+        CtOption::new(u, is_finite & is_preimage)
+    });
+
+    // Repeated roots are only kept once.
+    for j in 1..preimages.len() {
+        let mut is_repeated = Choice::from(0);
+        for i in 0..j {
+            is_repeated |= preimages[i].is_some() & candidates[i].ct_eq(&candidates[j]);
+        }
+        preimages[j] = CtOption::new(candidates[j], preimages[j].is_some() & !is_repeated);
+    }
+    preimages
+}
+
+/// Encoding of curve points as byte strings that are indistinguishable from
+/// uniformly random ones, with the Elligator Squared construction of
+/// https://eprint.iacr.org/2014/043 over the SvdW map.
+///
+/// A point `P` is encoded as a random pair `(u, v)` with
+/// `map_to_curve(u) + map_to_curve(v) = P`, which is uniformly distributed
+/// when `P` is. Every field element is then written as a random
+/// little-endian integer congruent to it, uniform among the integers of the
+/// size of its representation.
+pub trait ElligatorSquared: CurveExt {
+    /// The SvdW map of the curve.
+    fn map_to_curve(u: Self::Base) -> CtOption<Self>;
+
+    /// Returns the candidate preimages of `self` under
+    /// [`ElligatorSquared::map_to_curve`]. The valid ones are distinct and
+    /// cover all the preimages, except for the at most four `u` where the map
+    /// is exceptional.
+    fn map_to_curve_inverse(&self) -> [CtOption<Self::Base>; 8];
+
+    /// Encodes `self` into twice the length of the representation of
+    /// `Self::Base`. The encoding is randomized and runs in variable time.
+    fn encode_uniform(&self, mut rng: impl RngCore) -> Vec<u8> {
+        loop {
+            let u = Self::Base::random(&mut rng);
+            let q = Self::map_to_curve(u).map(|q| *self - q);
+            let q = unwrap_or_identity(q);
+
+            // Every preimage of `q` is picked with probability `1 / 8`.
+            let j = (rng.next_u32() % 8) as usize;
+            let v = q.map_to_curve_inverse()[j];
+            if bool::from(v.is_none()) {
+                continue;
+            }
+
+            // An overflowing lift restarts the whole encoding, so that the
+            // output remains uniform.
+            if let (Some(u), Some(v)) = (lift(u, &mut rng), lift(v.unwrap(), &mut rng)) {
+                return [u, v].concat();
+            }
+        }
+    }
+
+    /// Decodes a point from the output of
+    /// [`ElligatorSquared::encode_uniform`]. Every string of the right length
+    /// decodes to a point; `None` is only returned for other lengths.
+    fn decode_uniform(bytes: &[u8]) -> Option<Self>
+    where
+        Self::Base: FromUniformBytes<64>,
+    {
+        let len = <Self::Base as PrimeField>::Repr::default().as_ref().len();
+        if bytes.len() != 2 * len {
+            return None;
+        }
+
+        let [u, v] = [&bytes[..len], &bytes[len..]].map(|bytes| {
+            let mut wide = [0u8; 64];
+            wide[..len].copy_from_slice(bytes);
+            Self::Base::from_uniform_bytes(&wide)
+        });
+        let p = Self::map_to_curve(u).and_then(|p| Self::map_to_curve(v).map(|q| p + q));
+        Some(unwrap_or_identity(p))
+    }
+}
+
+/// Returns the little-endian integer `u + k * p`, for the modulus `p` of `F`
+/// and a uniformly random `k` such that `k * p` fits in the representation of
+/// `F`, or `None` if `u + k * p` does not fit in it.
+fn lift<F: PrimeField>(u: F, mut rng: impl RngCore) -> Option<Vec<u8>> {
+    // Adds `b` to `a` and returns the carry.
+    let add = |a: &mut [u8], b: &[u8]| {
+        let mut carry = 0u16;
+        for (a, b) in a.iter_mut().zip(b.iter()) {
+            let sum = *a as u16 + *b as u16 + carry;
+            *a = sum as u8;
+            carry = sum >> 8;
+        }
+        carry != 0
+    };
+
+    let mut modulus = (-F::ONE).to_repr().as_ref().to_vec();
+    let mut one = vec![0u8; modulus.len()];
+    one[0] = 1;
+    add(&mut modulus, &one);
+
+    // Number of multiples of `p`, zero included, that fit.
+    let mut multiples = 1u32;
+    let mut multiple = vec![0u8; modulus.len()];
+    while !add(&mut multiple, &modulus) {
+        multiples += 1;
+    }
+
+    let mask = multiples.next_power_of_two() - 1;
+    let k = loop {
+        let k = rng.next_u32() & mask;
+        if k < multiples {
+            break k;
+        }
+    };
+
+    let mut lift = u.to_repr().as_ref().to_vec();
+    for _ in 0..k {
+        if add(&mut lift, &modulus) {
+            return None;
+        }
+    }
+    Some(lift)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{hash_to_field, hash_to_scalar, ExpandMessage, ExpandMsgXmd, ExpandMsgXof};
+    use crate::tests::fe_from_str;
+    use ff::FromUniformBytes;
+    use sha2::Sha256;
+    use sha3::{Shake128, Shake256};
+
+    fn to_hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    #[test]
+    fn test_expand_message_xmd_sha256() {
+        // Test vectors of https://datatracker.ietf.org/doc/html/rfc9380#appendix-K.1
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        let long_dst = [
+            &b"QUUX-V01-CS02-with-expander-SHA256-128-long-DST-"[..],
+            &[b'1'; 208],
+        ]
+        .concat();
+
+        for (dst, message, len_in_bytes, expected) in [
+            (
+                &dst[..],
+                &b""[..],
+                0x20,
+                "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235",
+            ),
+            (
+                dst,
+                b"abc",
+                0x20,
+                "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615",
+            ),
+            (
+                dst,
+                b"abcdef0123456789",
+                0x20,
+                "eff31487c770a893cfb36f912fbfcbff40d5661771ca4b2cb4eafe524333f5c1",
+            ),
+            (
+                dst,
+                b"abc",
+                0x80,
+                "abba86a6129e366fc877aab32fc4ffc70120d8996c88aee2fe4b32d6c7b6437a\
+                 647e6c3163d40b76a73cf6a5674ef1d890f95b664ee0afa5359a5c4e07985635\
+                 bbecbac65d747d3d2da7ec2b8221b17b0ca9dc8a1ac1c07ea6a1e60583e2cb00\
+                 058e77b7b72a298425cd1b941ad4ec65e8afc50303a22c0f99b0509b4c895f40",
+            ),
+            (
+                &long_dst,
+                b"",
+                0x20,
+                "e8dc0c8b686b7ef2074086fbdd2f30e3f8bfbd3bdf177f73f04b97ce618a3ed3",
+            ),
+            (
+                &long_dst,
+                b"abc",
+                0x20,
+                "52dbf4f36cf560fca57dedec2ad924ee9c266341d8f3d6afe5171733b16bbb12",
+            ),
+        ] {
+            let output = ExpandMsgXmd::<Sha256>::expand_message(message, dst, len_in_bytes);
+            assert_eq!(output.len(), len_in_bytes);
+            let output = output
+                .iter()
+                .map(|b| format!("{b:02x}"))
+                .collect::<String>();
+            assert_eq!(output, expected);
+        }
+    }
+
+    #[test]
+    fn test_expand_message_xof_shake128() {
+        // Test vectors of https://datatracker.ietf.org/doc/html/rfc9380#appendix-K.3
+        let dst = b"QUUX-V01-CS02-with-expander-SHAKE128";
+        let long_dst = [
+            &b"QUUX-V01-CS02-with-expander-SHAKE128-long-DST-"[..],
+            &[b'1'; 210],
+        ]
+        .concat();
+
+        for (dst, message, len_in_bytes, expected) in [
+            (
+                &dst[..],
+                &b""[..],
+                0x20,
+                "86518c9cd86581486e9485aa74ab35ba150d1c75c88e26b7043e44e2acd735a2",
+            ),
+            (
+                dst,
+                b"abc",
+                0x20,
+                "8696af52a4d862417c0763556073f47bc9b9ba43c99b505305cb1ec04a9ab468",
+            ),
+            (
+                dst,
+                b"abc",
+                0x80,
+                "c952f0c8e529ca8824acc6a4cab0e782fc3648c563ddb00da7399f2ae35654f4\
+                 860ec671db2356ba7baa55a34a9d7f79197b60ddae6e64768a37d699a7832349\
+                 6db3878c8d64d909d0f8a7de4927dcab0d3dbbc26cb20a49eceb0530b431cdf4\
+                 7bc8c0fa3e0d88f53b318b6739fbed7d7634974f1b5c386d6230c76260d5337a",
+            ),
+            (
+                &long_dst,
+                b"",
+                0x20,
+                "827c6216330a122352312bccc0c8d6e7a146c5257a776dbd9ad9d75cd880fc53",
+            ),
+            (
+                &long_dst,
+                b"abc",
+                0x20,
+                "690c8d82c7213b4282c6cb41c00e31ea1d3e2005f93ad19bbf6da40f15790c5c",
+            ),
+        ] {
+            let output = ExpandMsgXof::<Shake128, 128>::expand_message(message, dst, len_in_bytes);
+            assert_eq!(output.len(), len_in_bytes);
+            assert_eq!(to_hex(&output), expected);
+        }
+    }
+
+    #[test]
+    fn test_expand_message_xof_shake256() {
+        // Test vectors of https://datatracker.ietf.org/doc/html/rfc9380#appendix-K.4
+        let dst = b"QUUX-V01-CS02-with-expander-SHAKE256";
+        for (message, expected) in [
+            (
+                &b""[..],
+                "2ffc05c48ed32b95d72e807f6eab9f7530dd1c2f013914c8fed38c5ccc15ad76",
+            ),
+            (
+                b"abc",
+                "b39e493867e2767216792abce1f2676c197c0692aed061560ead251821808e07",
+            ),
+        ] {
+            let output = ExpandMsgXof::<Shake256, 256>::expand_message(message, dst, 0x20);
+            assert_eq!(to_hex(&output), expected);
+        }
+    }
+
+    fn run_hash_to_field<F: FromUniformBytes<64>>(dst: &[u8], message: &[u8], expected: &[&str]) {
+        let output = hash_to_field::<F>(dst, message, expected.len());
+        let expected = expected.iter().map(fe_from_str).collect::<Vec<F>>();
+        assert_eq!(output, expected);
+
+        // The requested length is part of the expansion input, so the
+        // outputs of different counts are unrelated.
+        let scalar = hash_to_scalar::<F>(dst, message);
+        assert_eq!(scalar, hash_to_field::<F>(dst, message, 1)[0]);
+        if expected.len() > 1 {
+            assert_ne!(scalar, expected[0]);
+        }
+        assert!(hash_to_field::<F>(dst, message, 0).is_empty());
+    }
+
+    #[test]
+    fn test_hash_to_field() {
+        // Base fields, test vectors of
+        // https://datatracker.ietf.org/doc/html/rfc9380#appendix-J.1.1 and
+        // https://datatracker.ietf.org/doc/html/rfc9380#appendix-J.8.1
+        run_hash_to_field::<crate::secp256r1::Fp>(
+            b"QUUX-V01-CS02-with-P256_XMD:SHA-256_SSWU_RO_",
+            b"",
+            &[
+                "0xad5342c66a6dd0ff080df1da0ea1c04b96e0330dd89406465eeba11582515009",
+                "0x8c0f1d43204bd6f6ea70ae8013070a1518b43873bcd850aafa0a9e220e2eea5a",
+            ],
+        );
+        run_hash_to_field::<crate::secp256k1::Fp>(
+            b"QUUX-V01-CS02-with-secp256k1_XMD:SHA-256_SSWU_RO_",
+            b"abc",
+            &[
+                "0x128aab5d3679a1f7601e3bdf94ced1f43e491f544767e18a4873f397b08a2b61",
+                "0x5897b65da3b595a813d0fdcc75c895dc531be76a03518b044daaa0f2e4689e00",
+            ],
+        );
+
+        // Scalar field of P-256, the `DeriveKeyPair` vectors of
+        // https://datatracker.ietf.org/doc/html/rfc9497#appendix-A.3, whose
+        // secret keys are `HashToScalar` outputs for the OPRF, VOPRF and
+        // POPRF modes.
+        let seed = [0xa3; 32];
+        let key_info = b"test key";
+        let derive_input = [&seed[..], &[0, key_info.len() as u8], key_info, &[0]].concat();
+        for (mode, expected) in [
+            (
+                0,
+                "0x159749d750713afe245d2d39ccfaae8381c53ce92d098a9375ee70739c7ac0bf",
+            ),
+            (
+                1,
+                "0xca5d94c8807817669a51b196c34c1b7f8442fde4334a7121ae4736364312fca6",
+            ),
+            (
+                2,
+                "0x6ad2173efa689ef2c27772566ad7ff6e2d59b3b196f00219451fb2c89ee4dae2",
+            ),
+        ] {
+            let dst = [&b"DeriveKeyPairOPRFV1-"[..], &[mode], b"-P256-SHA256"].concat();
+            run_hash_to_field::<crate::secp256r1::Fq>(&dst, &derive_input, &[expected]);
+        }
+
+        // Fields of at most 256 bits are reduced from 48 bytes, and SHA-256
+        // expands to at most 255 * 32 bytes, i.e. 170 elements.
+        let dst = b"halo2curves-hash-to-scalar";
+        assert_eq!(
+            hash_to_field::<crate::bn256::Fr>(dst, b"abc", 170).len(),
+            170
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_hash_to_field_too_long() {
+        hash_to_field::<crate::bn256::Fr>(b"halo2curves-hash-to-scalar", b"abc", 171);
+    }
+}
@@ -1,7 +1,9 @@
 mod arithmetic;
+pub mod fixed_base;
 pub mod hash_to_curve;
 #[macro_use]
 pub mod legendre;
+pub mod msm;
 pub mod serde;
 
 pub mod bn256;
@@ -13,8 +15,16 @@ pub mod secq256k1;
 
 #[macro_use]
 mod derive;
+pub use arithmetic::{CurveAffineExt, CurveEndo};
 pub use pasta_curves::arithmetic::{Coordinates, CurveAffine, CurveExt};
 
+// Used by the expansion of `new_curve_impl!`, which must not depend on the
+// features or dependencies of the crate invoking it.
+#[doc(hidden)]
+pub use arithmetic::{batch_normalize_with, wnaf_mul};
+#[doc(hidden)]
+pub use lazy_static;
+
 // Re-export ff and group to simplify down stream dependencies
 #[cfg(feature = "reexport")]
 pub use ff;
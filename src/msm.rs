@@ -0,0 +1,572 @@
+//! Multi-scalar multiplication over any [`CurveAffine`] implementation.
+//!
+//! The implementation is the windowed bucket method (Pippenger). Scalars are
+//! read through their `to_repr()` encoding, which is little-endian for every
+//! scalar field in this crate.
+//!
+//! With the `multicore` feature enabled, [`msm`] splits large inputs across
+//! the rayon thread pool and merges the partial sums. Since group arithmetic
+//! is exact, the result is identical to the one of [`msm_serial`].
+//!
+//! [`msm_batch_affine`] accumulates the buckets with affine additions instead,
+//! sharing one field inversion across all the additions of a round through
+//! [`CurveAffineExt::batch_add_assign`].
+//!
+//! For a handful of terms, such as the `a * P + b * Q` of signature
+//! verification, the bucket method does not pay off. [`straus`] interleaves
+//! the wNAF expansions of all the scalars so that the doublings are shared,
+//! and [`straus_endo`] additionally halves their number with the GLV
+//! decomposition of [`CurveEndo`].
+
+use crate::arithmetic::{wnaf_add, wnaf_form, wnaf_table};
+use crate::{CurveAffineExt, CurveEndo};
+use ff::PrimeField;
+use group::Group;
+use pasta_curves::arithmetic::{CurveAffine, CurveExt};
+
+/// Returns the `c`-bit window of `bytes` that starts at bit `segment * c`.
+fn get_at<F: PrimeField>(segment: usize, c: usize, bytes: &F::Repr) -> usize {
+    let skip_bits = segment * c;
+    let skip_bytes = skip_bits / 8;
+    let bytes = bytes.as_ref();
+
+    if skip_bytes >= bytes.len() {
+        return 0;
+    }
+
+    let mut v = [0; 8];
+    for (v, o) in v.iter_mut().zip(bytes[skip_bytes..].iter()) {
+        *v = *o;
+    }
+
+    let mut tmp = u64::from_le_bytes(v);
+    tmp >>= skip_bits - (skip_bytes * 8);
+    tmp %= 1 << c;
+
+    tmp as usize
+}
+
+/// Picks the window size of the bucket method for an input of `n` terms.
+fn window_size(n: usize) -> usize {
+    if n < 4 {
+        1
+    } else if n < 32 {
+        3
+    } else {
+        (f64::from(n as u32)).ln().ceil() as usize
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Bucket<C: CurveAffine> {
+    None,
+    Affine(C),
+    Projective(C::Curve),
+}
+
+impl<C: CurveAffine> Bucket<C> {
+    fn add_assign(&mut self, other: &C) {
+        *self = match *self {
+            Bucket::None => Bucket::Affine(*other),
+            Bucket::Affine(a) => Bucket::Projective(a + *other),
+            Bucket::Projective(mut a) => {
+                a += *other;
+                Bucket::Projective(a)
+            }
+        }
+    }
+
+    fn add(self, mut other: C::Curve) -> C::Curve {
+        match self {
+            Bucket::None => other,
+            Bucket::Affine(a) => {
+                other += a;
+                other
+            }
+            Bucket::Projective(a) => other + a,
+        }
+    }
+}
+
+/// Performs a multi-scalar multiplication on a single thread and adds the
+/// result to `acc`.
+///
+/// Panics if `coeffs` and `bases` have different lengths.
+pub fn msm_serial<C: CurveAffine>(coeffs: &[C::Scalar], bases: &[C], acc: &mut C::Curve) {
+    assert_eq!(coeffs.len(), bases.len());
+
+    let coeffs: Vec<_> = coeffs.iter().map(|a| a.to_repr()).collect();
+
+    let c = window_size(bases.len());
+    let num_bits = C::Scalar::NUM_BITS as usize;
+    let segments = (num_bits + c - 1) / c;
+
+    let mut buckets: Vec<Bucket<C>> = vec![Bucket::None; (1 << c) - 1];
+
+    for current_segment in (0..segments).rev() {
+        for _ in 0..c {
+            *acc = acc.double();
+        }
+
+        buckets.iter_mut().for_each(|b| *b = Bucket::None);
+
+        for (coeff, base) in coeffs.iter().zip(bases.iter()) {
+            let coeff = get_at::<C::Scalar>(current_segment, c, coeff);
+            if coeff != 0 {
+                buckets[coeff - 1].add_assign(base);
+            }
+        }
+
+        // Summation by parts
+        // e.g. 3a + 2b + 1c = a +
+        //                    (a) + b +
+        //                    ((a) + b) + c
+        let mut running_sum = C::Curve::identity();
+        for exp in buckets.iter().rev() {
+            running_sum = exp.add(running_sum);
+            *acc += &running_sum;
+        }
+    }
+}
+
+/// Splits the terms into one chunk per thread, runs [`msm_serial`] on each
+/// chunk in parallel and returns the sum of the partial results.
+///
+/// Panics if `coeffs` and `bases` have different lengths.
+#[cfg(feature = "multicore")]
+pub fn msm_parallel<C: CurveAffine>(coeffs: &[C::Scalar], bases: &[C]) -> C::Curve {
+    use rayon::prelude::*;
+
+    assert_eq!(coeffs.len(), bases.len());
+
+    let num_threads = rayon::current_num_threads();
+    let chunk = (bases.len() + num_threads - 1) / num_threads;
+    if chunk == 0 {
+        return C::Curve::identity();
+    }
+
+    coeffs
+        .par_chunks(chunk)
+        .zip(bases.par_chunks(chunk))
+        .map(|(coeffs, bases)| {
+            let mut acc = C::Curve::identity();
+            msm_serial(coeffs, bases, &mut acc);
+            acc
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .sum()
+}
+
+/// Below this number of terms [`msm`] does not split the work across threads.
+#[cfg(feature = "multicore")]
+const PARALLEL_THRESHOLD: usize = 1 << 10;
+
+/// Computes `sum_i coeffs[i] * bases[i]`.
+///
+/// The window size of the bucket method is chosen from the number of terms.
+/// With the `multicore` feature, inputs of at least `2^10` terms are
+/// processed by [`msm_parallel`].
+/// This function is not constant time.
+///
+/// Panics if `coeffs` and `bases` have different lengths.
+pub fn msm<C: CurveAffine>(coeffs: &[C::Scalar], bases: &[C]) -> C::Curve {
+    #[cfg(feature = "multicore")]
+    if bases.len() >= PARALLEL_THRESHOLD && rayon::current_num_threads() > 1 {
+        return msm_parallel(coeffs, bases);
+    }
+
+    let mut acc = C::Curve::identity();
+    msm_serial(coeffs, bases, &mut acc);
+    acc
+}
+
+/// Sums the points of every bucket with batched affine additions.
+///
+/// `points` holds the points of all the buckets, bucket after bucket, and
+/// `sizes[i]` is the number of points in the `i`-th bucket. Each round adds
+/// the points of every bucket pairwise, so all the buckets are reduced to a
+/// single point after `log2` of the largest bucket size rounds. On return,
+/// `points[i]` is the sum of the `i`-th bucket, or the identity if it was
+/// empty.
+fn reduce_buckets<C: CurveAffineExt>(points: &mut Vec<C>, sizes: &mut [usize]) {
+    let mut lhs = Vec::with_capacity(points.len() / 2);
+    let mut rhs = Vec::with_capacity(points.len() / 2);
+
+    while sizes.iter().any(|&size| size > 1) {
+        lhs.clear();
+        rhs.clear();
+        let mut offset = 0;
+        for &size in sizes.iter() {
+            for pair in points[offset..offset + size].chunks_exact(2) {
+                lhs.push(pair[0]);
+                rhs.push(pair[1]);
+            }
+            offset += size;
+        }
+
+        C::batch_add_assign(&mut lhs, &rhs);
+
+        // Rebuild the buckets from the sums and the unpaired points.
+        let mut sums = lhs.iter();
+        let mut next = Vec::with_capacity(lhs.len() + sizes.len());
+        let mut offset = 0;
+        for size in sizes.iter_mut() {
+            next.extend(sums.by_ref().take(*size / 2));
+            if *size % 2 == 1 {
+                next.push(points[offset + *size - 1]);
+            }
+            offset += *size;
+            *size = (*size + 1) / 2;
+        }
+        *points = next;
+    }
+
+    // Every bucket now holds at most one point.
+    let mut reduced = Vec::with_capacity(sizes.len());
+    let mut offset = 0;
+    for &size in sizes.iter() {
+        if size == 0 {
+            reduced.push(C::identity());
+        } else {
+            reduced.push(points[offset]);
+        }
+        offset += size;
+    }
+    *points = reduced;
+}
+
+/// Computes `sum_i coeffs[i] * bases[i]` with the bucket method, accumulating
+/// the buckets of each window in affine coordinates.
+///
+/// The points of a window are sorted by bucket and the buckets are reduced
+/// pairwise in rounds, each round costing a single field inversion. This is
+/// faster than [`msm_serial`] when the buckets are large, that is for large
+/// inputs.
+/// This function is not constant time.
+///
+/// Panics if `coeffs` and `bases` have different lengths.
+pub fn msm_batch_affine<C: CurveAffineExt>(coeffs: &[C::Scalar], bases: &[C]) -> C::Curve {
+    assert_eq!(coeffs.len(), bases.len());
+
+    let coeffs: Vec<_> = coeffs.iter().map(|a| a.to_repr()).collect();
+
+    let c = window_size(bases.len());
+    let num_bits = C::Scalar::NUM_BITS as usize;
+    let segments = (num_bits + c - 1) / c;
+
+    let mut acc = C::Curve::identity();
+    for current_segment in (0..segments).rev() {
+        for _ in 0..c {
+            acc = acc.double();
+        }
+
+        let digits: Vec<_> = coeffs
+            .iter()
+            .map(|coeff| get_at::<C::Scalar>(current_segment, c, coeff))
+            .collect();
+
+        // Counting sort of the bases by bucket, dropping the zero digits.
+        let mut sizes = vec![0; (1 << c) - 1];
+        for &digit in digits.iter().filter(|&&digit| digit != 0) {
+            sizes[digit - 1] += 1;
+        }
+        let mut offsets: Vec<_> = sizes
+            .iter()
+            .scan(0, |offset, &size| {
+                let start = *offset;
+                *offset += size;
+                Some(start)
+            })
+            .collect();
+        let mut points = vec![C::identity(); sizes.iter().sum()];
+        for (&digit, base) in digits.iter().zip(bases.iter()) {
+            if digit != 0 {
+                points[offsets[digit - 1]] = *base;
+                offsets[digit - 1] += 1;
+            }
+        }
+
+        reduce_buckets(&mut points, &mut sizes);
+
+        // Summation by parts
+        let mut running_sum = C::Curve::identity();
+        for bucket in points.iter().rev() {
+            running_sum += bucket;
+            acc += &running_sum;
+        }
+    }
+    acc
+}
+
+/// Window width of the wNAF expansions used by [`straus`] and [`straus_endo`].
+const STRAUS_WINDOW: usize = 4;
+
+/// Computes `sum_i bases[i] * nafs[i]`, sharing the doublings between all the
+/// terms.
+fn straus_wnaf<C: Group>(nafs: &[Vec<i64>], bases: &[C]) -> C {
+    let tables: Vec<_> = bases
+        .iter()
+        .map(|base| wnaf_table(base, STRAUS_WINDOW))
+        .collect();
+    let len = nafs.iter().map(Vec::len).max().unwrap_or(0);
+
+    let mut acc = C::identity();
+    for i in (0..len).rev() {
+        acc = acc.double();
+        for (naf, table) in nafs.iter().zip(tables.iter()) {
+            if let Some(&digit) = naf.get(i) {
+                wnaf_add(&mut acc, table, digit);
+            }
+        }
+    }
+    acc
+}
+
+/// Computes `sum_i coeffs[i] * bases[i]` with Straus' interleaved method.
+///
+/// This is meant for a small number of terms, up to about 16, where it is
+/// faster than [`msm`].
+/// This function is not constant time.
+///
+/// Panics if `coeffs` and `bases` have different lengths.
+pub fn straus<C: CurveExt>(coeffs: &[C::ScalarExt], bases: &[C]) -> C {
+    assert_eq!(coeffs.len(), bases.len());
+
+    let nafs: Vec<_> = coeffs
+        .iter()
+        .map(|coeff| wnaf_form(coeff.to_repr().as_ref(), STRAUS_WINDOW))
+        .collect();
+    straus_wnaf(&nafs, bases)
+}
+
+/// Computes `sum_i coeffs[i] * bases[i]` with Straus' interleaved method,
+/// after splitting every term into two terms with 128-bit scalars through
+/// [`CurveEndo::decompose_scalar`].
+///
+/// This function is not constant time.
+///
+/// Panics if `coeffs` and `bases` have different lengths.
+pub fn straus_endo<C: CurveEndo>(coeffs: &[C::ScalarExt], bases: &[C]) -> C {
+    assert_eq!(coeffs.len(), bases.len());
+
+    let mut nafs = Vec::with_capacity(2 * coeffs.len());
+    let mut split_bases = Vec::with_capacity(2 * bases.len());
+    for (coeff, base) in coeffs.iter().zip(bases.iter()) {
+        let (k1, k1_neg, k2, k2_neg) = C::decompose_scalar(coeff);
+        let (p1, p2) = base.glv_bases(k1_neg, k2_neg);
+        nafs.push(wnaf_form(&k1.to_le_bytes(), STRAUS_WINDOW));
+        nafs.push(wnaf_form(&k2.to_le_bytes(), STRAUS_WINDOW));
+        split_bases.push(p1);
+        split_bases.push(p2);
+    }
+    straus_wnaf(&nafs, &split_bases)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{msm, msm_batch_affine, straus, straus_endo};
+    use crate::bn256::{Fr, G1Affine, G2Affine};
+    use crate::{CurveAffineExt, CurveEndo, CurveExt};
+    use ff::{Field, WithSmallOrderMulGroup};
+    use group::{Curve, Group};
+    use pasta_curves::arithmetic::CurveAffine;
+    use rand_core::OsRng;
+
+    fn naive_msm<C: CurveAffine>(coeffs: &[C::Scalar], bases: &[C]) -> C::Curve {
+        coeffs
+            .iter()
+            .zip(bases.iter())
+            .fold(C::Curve::identity(), |acc, (coeff, base)| {
+                acc + *base * *coeff
+            })
+    }
+
+    fn random_terms<C: CurveAffine>(n: usize) -> (Vec<C::Scalar>, Vec<C>) {
+        let coeffs: Vec<_> = (0..n).map(|_| C::Scalar::random(OsRng)).collect();
+        let bases: Vec<_> = (0..n)
+            .map(|_| C::Curve::random(OsRng).to_affine())
+            .collect();
+        (coeffs, bases)
+    }
+
+    fn run_msm_cross<C: CurveAffine>(max_k: usize) {
+        for k in 0..=max_k {
+            let (coeffs, bases) = random_terms::<C>(1 << k);
+            assert_eq!(msm(&coeffs, &bases), naive_msm(&coeffs, &bases));
+        }
+    }
+
+    fn run_msm_edge_cases<C: CurveAffine>() {
+        assert_eq!(msm::<C>(&[], &[]), C::Curve::identity());
+
+        let (mut coeffs, mut bases) = random_terms::<C>(40);
+        coeffs[0] = C::Scalar::ZERO;
+        coeffs[1] = C::Scalar::ONE;
+        coeffs[2] = -C::Scalar::ONE;
+        bases[3] = C::identity();
+        bases[5] = bases[4];
+        bases[6] = -bases[4];
+        coeffs[7] = coeffs[8];
+        bases[7] = bases[8];
+        assert_eq!(msm(&coeffs, &bases), naive_msm(&coeffs, &bases));
+    }
+
+    fn run_batch_add<C: CurveAffineExt>() {
+        let (_, mut acc) = random_terms::<C>(20);
+        let (_, mut rhs) = random_terms::<C>(20);
+        acc[0] = C::identity();
+        rhs[1] = C::identity();
+        acc[2] = C::identity();
+        rhs[2] = C::identity();
+        rhs[3] = acc[3];
+        rhs[4] = -acc[4];
+        rhs[5] = acc[6];
+        acc[7] = acc[6];
+        rhs[7] = acc[6];
+
+        let expected: Vec<_> = acc
+            .iter()
+            .zip(rhs.iter())
+            .map(|(p, q)| (*p + *q).to_affine())
+            .collect();
+        C::batch_add_assign(&mut acc, &rhs);
+        assert_eq!(acc, expected);
+
+        C::batch_add_assign(&mut [], &[]);
+    }
+
+    fn run_msm_batch_affine<C: CurveAffineExt>(max_k: usize) {
+        for k in 0..=max_k {
+            let (coeffs, bases) = random_terms::<C>(1 << k);
+            assert_eq!(
+                msm_batch_affine(&coeffs, &bases),
+                naive_msm(&coeffs, &bases)
+            );
+        }
+
+        let (mut coeffs, mut bases) = random_terms::<C>(40);
+        coeffs[0] = C::Scalar::ZERO;
+        bases[3] = C::identity();
+        bases[5] = bases[4];
+        coeffs[5] = coeffs[4];
+        bases[6] = -bases[4];
+        coeffs[6] = coeffs[4];
+        coeffs[7] = coeffs[8];
+        bases[7] = bases[8];
+        assert_eq!(
+            msm_batch_affine(&coeffs, &bases),
+            naive_msm(&coeffs, &bases)
+        );
+    }
+
+    #[test]
+    fn test_batch_add() {
+        run_batch_add::<G1Affine>();
+        run_batch_add::<G2Affine>();
+        run_batch_add::<crate::secp256r1::Secp256r1Affine>();
+        run_batch_add::<crate::pasta::EpAffine>();
+    }
+
+    #[test]
+    fn test_msm_batch_affine() {
+        run_msm_batch_affine::<G1Affine>(10);
+        run_msm_batch_affine::<G2Affine>(6);
+        run_msm_batch_affine::<crate::secp256r1::Secp256r1Affine>(6);
+        run_msm_batch_affine::<crate::pasta::EqAffine>(6);
+    }
+
+    fn random_points<C: CurveExt>(n: usize) -> (Vec<C::ScalarExt>, Vec<C>) {
+        let coeffs = (0..n).map(|_| C::ScalarExt::random(OsRng)).collect();
+        let bases = (0..n).map(|_| C::random(OsRng)).collect();
+        (coeffs, bases)
+    }
+
+    fn naive_sum<C: CurveExt>(coeffs: &[C::ScalarExt], bases: &[C]) -> C {
+        coeffs
+            .iter()
+            .zip(bases.iter())
+            .fold(C::identity(), |acc, (coeff, base)| acc + *base * *coeff)
+    }
+
+    fn run_straus<C: CurveExt>() {
+        assert_eq!(straus::<C>(&[], &[]), C::identity());
+        for n in 1..=16 {
+            let (coeffs, bases) = random_points::<C>(n);
+            assert_eq!(straus(&coeffs, &bases), naive_sum(&coeffs, &bases));
+        }
+
+        let p = C::random(OsRng);
+        let coeffs = [C::ScalarExt::ZERO, C::ScalarExt::ONE, -C::ScalarExt::ONE];
+        assert_eq!(straus(&coeffs, &[p, p, -p]), p.double());
+    }
+
+    fn run_straus_endo<C: CurveEndo>() {
+        run_straus::<C>();
+
+        assert_eq!(straus_endo::<C>(&[], &[]), C::identity());
+        for n in 1..=16 {
+            let (coeffs, bases) = random_points::<C>(n);
+            assert_eq!(straus_endo(&coeffs, &bases), naive_sum(&coeffs, &bases));
+        }
+
+        let p = C::random(OsRng);
+        let coeffs = [
+            C::ScalarExt::ZERO,
+            C::ScalarExt::ONE,
+            -C::ScalarExt::ONE,
+            C::ScalarExt::ZETA,
+        ];
+        let bases = [p, p, -p, C::identity()];
+        assert_eq!(straus_endo(&coeffs, &bases), p.double());
+    }
+
+    #[test]
+    fn test_straus() {
+        run_straus_endo::<crate::bn256::G1>();
+        run_straus_endo::<crate::bn256::G2>();
+        run_straus_endo::<crate::grumpkin::G1>();
+        run_straus_endo::<crate::secp256k1::Secp256k1>();
+        run_straus_endo::<crate::pasta::Ep>();
+        run_straus::<crate::secp256r1::Secp256r1>();
+    }
+
+    #[test]
+    fn test_msm_cross() {
+        run_msm_cross::<G1Affine>(10);
+        run_msm_cross::<G2Affine>(6);
+        run_msm_cross::<crate::grumpkin::G1Affine>(6);
+        run_msm_cross::<crate::secp256k1::Secp256k1Affine>(6);
+        run_msm_cross::<crate::secp256r1::Secp256r1Affine>(6);
+        run_msm_cross::<crate::secq256k1::Secq256k1Affine>(6);
+        run_msm_cross::<crate::pasta::EpAffine>(6);
+        run_msm_cross::<crate::pasta::EqAffine>(6);
+    }
+
+    #[test]
+    fn test_msm_edge_cases() {
+        run_msm_edge_cases::<G1Affine>();
+        run_msm_edge_cases::<crate::secp256r1::Secp256r1Affine>();
+        run_msm_edge_cases::<crate::pasta::EpAffine>();
+
+        let g = G1Affine::generator();
+        let coeffs = [Fr::from(2), Fr::from(3)];
+        assert_eq!(msm(&coeffs, &[g, g]), g * Fr::from(5));
+    }
+
+    #[cfg(feature = "multicore")]
+    #[test]
+    fn test_msm_parallel() {
+        use super::{msm_parallel, msm_serial};
+        use crate::bn256::G1;
+
+        assert_eq!(msm_parallel::<G1Affine>(&[], &[]), G1::identity());
+        for k in [0, 1, 5, 11, 12] {
+            let (coeffs, bases) = random_terms::<G1Affine>(1 << k);
+            let mut serial = G1::identity();
+            msm_serial(&coeffs, &bases, &mut serial);
+            assert_eq!(msm_parallel(&coeffs, &bases), serial);
+            assert_eq!(msm(&coeffs, &bases), serial);
+        }
+    }
+}
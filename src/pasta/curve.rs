@@ -0,0 +1,680 @@
+use crate::arithmetic::{mul_512, sbb, CurveEndo, EndoParameters};
+use crate::endo;
+use crate::ff::WithSmallOrderMulGroup;
+use crate::ff::{Field, PrimeField};
+use crate::group::{prime::PrimeCurveAffine, Curve, Group as _, GroupEncoding};
+use crate::hash_to_curve::{
+    simple_svdw_isogeny_encode_to_curve, simple_svdw_isogeny_hash_to_curve,
+    simple_svdw_isogeny_hash_to_curve_with, EncodeToCurve, ExpandMessage, HashToCurveWith, Isogeny,
+};
+use crate::pasta::Fp;
+use crate::pasta::Fq;
+use crate::{Coordinates, CurveAffine, CurveExt};
+use core::cmp;
+use core::fmt::Debug;
+use core::iter::Sum;
+use core::ops::{Add, Mul, Neg, Sub};
+use rand::RngCore;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+
+#[cfg(feature = "derive_serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    impl_add_binop_specify_output, impl_binops_additive, impl_binops_additive_specify_output,
+    impl_binops_multiplicative, impl_binops_multiplicative_mixed, impl_sub_binop_specify_output,
+    new_curve_impl,
+};
+
+// Both curves have the generator `(-1, 2)`, as in `pasta_curves`.
+const PALLAS_GENERATOR_X: Fp = Fp::from_raw([
+    0x992d30ed00000000,
+    0x224698fc094cf91b,
+    0x0000000000000000,
+    0x4000000000000000,
+]);
+const PALLAS_GENERATOR_Y: Fp = Fp::from_raw([2, 0, 0, 0]);
+
+const PALLAS_A: Fp = Fp::from_raw([0, 0, 0, 0]);
+const PALLAS_B: Fp = Fp::from_raw([5, 0, 0, 0]);
+
+const VESTA_GENERATOR_X: Fq = Fq::from_raw([
+    0x8c46eb2100000000,
+    0x224698fc0994a8dd,
+    0x0000000000000000,
+    0x4000000000000000,
+]);
+const VESTA_GENERATOR_Y: Fq = Fq::from_raw([2, 0, 0, 0]);
+
+const VESTA_A: Fq = Fq::from_raw([0, 0, 0, 0]);
+const VESTA_B: Fq = Fq::from_raw([5, 0, 0, 0]);
+
+new_curve_impl!(
+    (pub),
+    Ep,
+    EpAffine,
+    false,
+    Fp,
+    Fq,
+    (PALLAS_GENERATOR_X, PALLAS_GENERATOR_Y),
+    PALLAS_A,
+    PALLAS_B,
+    "pallas",
+    |curve_id, domain_prefix| simple_svdw_isogeny_hash_to_curve(curve_id, domain_prefix, Ep::Z, PALLAS_ISOGENY),
+    custom_encoding,
+);
+
+new_curve_impl!(
+    (pub),
+    Eq,
+    EqAffine,
+    false,
+    Fq,
+    Fp,
+    (VESTA_GENERATOR_X, VESTA_GENERATOR_Y),
+    VESTA_A,
+    VESTA_B,
+    "vesta",
+    |curve_id, domain_prefix| simple_svdw_isogeny_hash_to_curve(curve_id, domain_prefix, Eq::Z, VESTA_ISOGENY),
+    custom_encoding,
+);
+
+impl group::cofactor::CofactorGroup for Ep {
+    type Subgroup = Ep;
+
+    fn clear_cofactor(&self) -> Self {
+        *self
+    }
+
+    fn into_subgroup(self) -> CtOption<Self::Subgroup> {
+        CtOption::new(self, 1.into())
+    }
+
+    fn is_torsion_free(&self) -> Choice {
+        1.into()
+    }
+}
+
+impl group::cofactor::CofactorGroup for Eq {
+    type Subgroup = Eq;
+
+    fn clear_cofactor(&self) -> Self {
+        *self
+    }
+
+    fn into_subgroup(self) -> CtOption<Self::Subgroup> {
+        CtOption::new(self, 1.into())
+    }
+
+    fn is_torsion_free(&self) -> Choice {
+        1.into()
+    }
+}
+
+// Generated using https://github.com/ConsenSys/gnark-crypto/blob/master/ecc/utils.go
+// with `pasta_curves::Fp::ZETA`
+// See https://github.com/demining/Endomorphism-Secp256k1/blob/main/README.md
+// to have more details about the endomorphism.
+const ENDO_PARAMS_EQ: EndoParameters = EndoParameters {
+    // round(b2/n)
+    gamma1: [0x32c49e4c00000003, 0x279a745902a2654e, 0x1, 0x0],
+    // round(-b1/n)
+    gamma2: [0x31f0256800000002, 0x4f34e8b2066389a4, 0x2, 0x0],
+    b1: [0x8cb1279300000001, 0x49e69d1640a89953, 0x0, 0x0],
+    b2: [0x0c7c095a00000001, 0x93cd3a2c8198e269, 0x0, 0x0],
+};
+
+// Generated using https://github.com/ConsenSys/gnark-crypto/blob/master/ecc/utils.go
+// with `pasta_curves::Fq::ZETA`
+// See https://github.com/demining/Endomorphism-Secp256k1/blob/main/README.md
+// to have more details about the endomorphism.
+const ENDO_PARAMS_EP: EndoParameters = EndoParameters {
+    // round(b2/n)
+    gamma1: [0x32c49e4bffffffff, 0x279a745902a2654e, 0x1, 0x0],
+    // round(-b1/n)
+    gamma2: [0x31f0256800000002, 0x4f34e8b2066389a4, 0x2, 0x0],
+    b1: [0x8cb1279300000000, 0x49e69d1640a89953, 0x0, 0x0],
+    b2: [0x0c7c095a00000001, 0x93cd3a2c8198e269, 0x0, 0x0],
+};
+
+endo!(Eq, Fp, ENDO_PARAMS_EQ);
+endo!(Ep, Fq, ENDO_PARAMS_EP);
+
+impl Ep {
+    /// Constants used for computing the isogeny from IsoEp to Ep.
+    pub const ISOGENY_CONSTANTS: [Fp; 13] = [
+        Fp::from_raw([
+            0x775f6034aaaaaaab,
+            0x4081775473d8375b,
+            0xe38e38e38e38e38e,
+            0x0e38e38e38e38e38,
+        ]),
+        Fp::from_raw([
+            0x8cf863b02814fb76,
+            0x0f93b82ee4b99495,
+            0x267c7ffa51cf412a,
+            0x3509afd51872d88e,
+        ]),
+        Fp::from_raw([
+            0x0eb64faef37ea4f7,
+            0x380af066cfeb6d69,
+            0x98c7d7ac3d98fd13,
+            0x17329b9ec5253753,
+        ]),
+        Fp::from_raw([
+            0xeebec06955555580,
+            0x8102eea8e7b06eb6,
+            0xc71c71c71c71c71c,
+            0x1c71c71c71c71c71,
+        ]),
+        Fp::from_raw([
+            0xc47f2ab668bcd71f,
+            0x9c434ac1c96b6980,
+            0x5a607fcce0494a79,
+            0x1d572e7ddc099cff,
+        ]),
+        Fp::from_raw([
+            0x2aa3af1eae5b6604,
+            0xb4abf9fb9a1fc81c,
+            0x1d13bf2a7f22b105,
+            0x325669becaecd5d1,
+        ]),
+        Fp::from_raw([
+            0x5ad985b5e38e38e4,
+            0x7642b01ad461bad2,
+            0x4bda12f684bda12f,
+            0x1a12f684bda12f68,
+        ]),
+        Fp::from_raw([
+            0xc67c31d8140a7dbb,
+            0x07c9dc17725cca4a,
+            0x133e3ffd28e7a095,
+            0x1a84d7ea8c396c47,
+        ]),
+        Fp::from_raw([
+            0x02e2be87d225b234,
+            0x1765e924f7459378,
+            0x303216cce1db9ff1,
+            0x3fb98ff0d2ddcadd,
+        ]),
+        Fp::from_raw([
+            0x93e53ab371c71c4f,
+            0x0ac03e8e134eb3e4,
+            0x7b425ed097b425ed,
+            0x025ed097b425ed09,
+        ]),
+        Fp::from_raw([
+            0x5a28279b1d1b42ae,
+            0x5941a3a4a97aa1b3,
+            0x0790bfb3506defb6,
+            0x0c02c5bcca0e6b7f,
+        ]),
+        Fp::from_raw([
+            0x4d90ab820b12320a,
+            0xd976bbfabbc5661d,
+            0x573b3d7f7d681310,
+            0x17033d3c60c68173,
+        ]),
+        Fp::from_raw([
+            0x992d30ecfffffde5,
+            0x224698fc094cf91b,
+            0x0000000000000000,
+            0x4000000000000000,
+        ]),
+    ];
+
+    /// Z = -13
+    pub const Z: Fp = Fp::from_raw([
+        0x992d30ecfffffff4,
+        0x224698fc094cf91b,
+        0x0000000000000000,
+        0x4000000000000000,
+    ]);
+}
+
+impl Eq {
+    /// Constants used for computing the isogeny from IsoEq to Eq.
+    pub const ISOGENY_CONSTANTS: [Fq; 13] = [
+        Fq::from_raw([
+            0x43cd42c800000001,
+            0x0205dd51cfa0961a,
+            0x8e38e38e38e38e39,
+            0x38e38e38e38e38e3,
+        ]),
+        Fq::from_raw([
+            0x8b95c6aaf703bcc5,
+            0x216b8861ec72bd5d,
+            0xacecf10f5f7c09a2,
+            0x1d935247b4473d17,
+        ]),
+        Fq::from_raw([
+            0xaeac67bbeb586a3d,
+            0xd59d03d23b39cb11,
+            0xed7ee4a9cdf78f8f,
+            0x18760c7f7a9ad20d,
+        ]),
+        Fq::from_raw([
+            0xfb539a6f0000002b,
+            0xe1c521a795ac8356,
+            0x1c71c71c71c71c71,
+            0x31c71c71c71c71c7,
+        ]),
+        Fq::from_raw([
+            0xb7284f7eaf21a2e9,
+            0xa3ad678129b604d3,
+            0x1454798a5b5c56b2,
+            0x0a2de485568125d5,
+        ]),
+        Fq::from_raw([
+            0xf169c187d2533465,
+            0x30cd6d53df49d235,
+            0x0c621de8b91c242a,
+            0x14735171ee542778,
+        ]),
+        Fq::from_raw([
+            0x6bef1642aaaaaaab,
+            0x5601f4709a8adcb3,
+            0xda12f684bda12f68,
+            0x12f684bda12f684b,
+        ]),
+        Fq::from_raw([
+            0x8bee58e5fb81de63,
+            0x21d910aefb03b31d,
+            0xd6767887afbe04d1,
+            0x2ec9a923da239e8b,
+        ]),
+        Fq::from_raw([
+            0x4986913ab4443034,
+            0x97a3ca5c24e9ea63,
+            0x66d1466e9de10e64,
+            0x19b0d87e16e25788,
+        ]),
+        Fq::from_raw([
+            0x8f64842c55555533,
+            0x8bc32d36fb21a6a3,
+            0x425ed097b425ed09,
+            0x1ed097b425ed097b,
+        ]),
+        Fq::from_raw([
+            0x58dfecce86b2745e,
+            0x06a767bfc35b5bac,
+            0x9e7eb64f890a820c,
+            0x2f44d6c801c1b8bf,
+        ]),
+        Fq::from_raw([
+            0xd43d449776f99d2f,
+            0x926847fb9ddd76a1,
+            0x252659ba2b546c7e,
+            0x3d59f455cafc7668,
+        ]),
+        Fq::from_raw([
+            0x8c46eb20fffffde5,
+            0x224698fc0994a8dd,
+            0x0000000000000000,
+            0x4000000000000000,
+        ]),
+    ];
+
+    /// Z = -13
+    pub const Z: Fq = Fq::from_raw([
+        0x8c46eb20fffffff4,
+        0x224698fc0994a8dd,
+        0x0000000000000000,
+        0x4000000000000000,
+    ]);
+}
+
+// The 3-isogenies to Pallas and Vesta from `y^2 = x^3 + A' * x + 1265`, as
+// in `pasta_curves`, whose constants list the coefficients from the leading
+// one down.
+macro_rules! pasta_isogeny {
+    ($curve:ident, $base:ident, $a:expr) => {
+        Isogeny {
+            a: $base::from_raw($a),
+            b: $base::from_raw([1265, 0, 0, 0]),
+            x_num: &[
+                $curve::ISOGENY_CONSTANTS[3],
+                $curve::ISOGENY_CONSTANTS[2],
+                $curve::ISOGENY_CONSTANTS[1],
+                $curve::ISOGENY_CONSTANTS[0],
+            ],
+            x_den: &[$curve::ISOGENY_CONSTANTS[5], $curve::ISOGENY_CONSTANTS[4]],
+            y_num: &[
+                $curve::ISOGENY_CONSTANTS[9],
+                $curve::ISOGENY_CONSTANTS[8],
+                $curve::ISOGENY_CONSTANTS[7],
+                $curve::ISOGENY_CONSTANTS[6],
+            ],
+            y_den: &[
+                $curve::ISOGENY_CONSTANTS[12],
+                $curve::ISOGENY_CONSTANTS[11],
+                $curve::ISOGENY_CONSTANTS[10],
+            ],
+        }
+    };
+}
+
+const PALLAS_ISOGENY: Isogeny<Fp> = pasta_isogeny!(
+    Ep,
+    Fp,
+    [
+        0x92bb4b0b657a014b,
+        0xb74134581a27a59f,
+        0x49be2d7258370742,
+        0x18354a2eb0ea8c9c,
+    ]
+);
+
+const VESTA_ISOGENY: Isogeny<Fq> = pasta_isogeny!(
+    Eq,
+    Fq,
+    [
+        0xc515ad7242eaa6b1,
+        0x9673928c7d01b212,
+        0x81639c4d96f78773,
+        0x267f9b2ee592271a,
+    ]
+);
+
+/// Returns a hasher of messages to Pallas, the simplified SWU map to
+/// iso-Pallas followed by the isogeny. Its outputs are those of
+/// `Ep::hash_to_curve` and of `pasta_curves`.
+#[allow(clippy::type_complexity)]
+pub fn pallas_hash_to_curve<'a>(domain_prefix: &'a str) -> Box<dyn Fn(&[u8]) -> Ep + 'a> {
+    simple_svdw_isogeny_hash_to_curve(Ep::CURVE_ID, domain_prefix, Ep::Z, PALLAS_ISOGENY)
+}
+
+/// Returns a hasher of messages to Vesta, see [`pallas_hash_to_curve`]. Its
+/// outputs are those of `Eq::hash_to_curve` and of `pasta_curves`.
+#[allow(clippy::type_complexity)]
+pub fn vesta_hash_to_curve<'a>(domain_prefix: &'a str) -> Box<dyn Fn(&[u8]) -> Eq + 'a> {
+    simple_svdw_isogeny_hash_to_curve(Eq::CURVE_ID, domain_prefix, Eq::Z, VESTA_ISOGENY)
+}
+
+impl EncodeToCurve for Ep {
+    fn encode_to_curve<'a>(domain_prefix: &'a str) -> Box<dyn Fn(&[u8]) -> Self + 'a> {
+        simple_svdw_isogeny_encode_to_curve(Self::CURVE_ID, domain_prefix, Ep::Z, PALLAS_ISOGENY)
+    }
+}
+
+impl HashToCurveWith for Ep {
+    fn hash_to_curve_with<'a, X: ExpandMessage + 'a>(
+        dst: &[u8],
+    ) -> Box<dyn Fn(&[u8]) -> Self + 'a> {
+        simple_svdw_isogeny_hash_to_curve_with::<Ep, X>(dst.to_vec(), Ep::Z, PALLAS_ISOGENY)
+    }
+}
+
+impl EncodeToCurve for Eq {
+    fn encode_to_curve<'a>(domain_prefix: &'a str) -> Box<dyn Fn(&[u8]) -> Self + 'a> {
+        simple_svdw_isogeny_encode_to_curve(Self::CURVE_ID, domain_prefix, Eq::Z, VESTA_ISOGENY)
+    }
+}
+
+impl HashToCurveWith for Eq {
+    fn hash_to_curve_with<'a, X: ExpandMessage + 'a>(
+        dst: &[u8],
+    ) -> Box<dyn Fn(&[u8]) -> Self + 'a> {
+        simple_svdw_isogeny_hash_to_curve_with::<Eq, X>(dst.to_vec(), Eq::Z, VESTA_ISOGENY)
+    }
+}
+
+// The compressed encoding of `pasta_curves`: the sign of `y` is stored in the
+// top bit of the last byte of `x`, and the identity is encoded as zero.
+macro_rules! impl_pasta_encoding {
+    ($name:ident, $name_affine:ident, $base:ident) => {
+        impl GroupEncoding for $name_affine {
+            type Repr = [u8; 32];
+
+            fn from_bytes(bytes: &Self::Repr) -> CtOption<Self> {
+                let mut tmp = *bytes;
+                let ysign = Choice::from(tmp[31] >> 7);
+                tmp[31] &= 0b0111_1111;
+
+                $base::from_repr(tmp).and_then(|x| {
+                    CtOption::new(Self::identity(), x.is_zero() & (!ysign)).or_else(|| {
+                        $name_affine::y2(x).sqrt().and_then(|y| {
+                            let sign = Choice::from(y.to_repr()[0] & 1);
+                            let y = $base::conditional_select(&y, &-y, ysign ^ sign);
+
+                            CtOption::new($name_affine { x, y }, Choice::from(1u8))
+                        })
+                    })
+                })
+            }
+
+            fn from_bytes_unchecked(bytes: &Self::Repr) -> CtOption<Self> {
+                Self::from_bytes(bytes)
+            }
+
+            fn to_bytes(&self) -> Self::Repr {
+                if bool::from(self.is_identity()) {
+                    [0; 32]
+                } else {
+                    let sign = (self.y.to_repr()[0] & 1) << 7;
+                    let mut xbytes = self.x.to_repr();
+                    xbytes[31] |= sign;
+                    xbytes
+                }
+            }
+        }
+
+        impl GroupEncoding for $name {
+            type Repr = [u8; 32];
+
+            fn from_bytes(bytes: &Self::Repr) -> CtOption<Self> {
+                $name_affine::from_bytes(bytes).map(Self::from)
+            }
+
+            fn from_bytes_unchecked(bytes: &Self::Repr) -> CtOption<Self> {
+                $name_affine::from_bytes(bytes).map(Self::from)
+            }
+
+            fn to_bytes(&self) -> Self::Repr {
+                $name_affine::from(self).to_bytes()
+            }
+        }
+    };
+}
+
+impl_pasta_encoding!(Ep, EpAffine, Fp);
+impl_pasta_encoding!(Eq, EqAffine, Fq);
+
+// Conversions to and from the `pasta_curves` points.
+macro_rules! pasta_curves_conversions {
+    ($name:ident, $name_affine:ident) => {
+        impl From<pasta_curves::$name> for $name {
+            fn from(value: pasta_curves::$name) -> Self {
+                let (x, y, z) = value.jacobian_coordinates();
+                $name::new_jacobian(x.into(), y.into(), z.into()).unwrap()
+            }
+        }
+
+        impl From<$name> for pasta_curves::$name {
+            fn from(value: $name) -> Self {
+                let (x, y, z) = value.jacobian_coordinates();
+                pasta_curves::$name::new_jacobian(x.into(), y.into(), z.into()).unwrap()
+            }
+        }
+
+        impl From<pasta_curves::$name_affine> for $name_affine {
+            fn from(value: pasta_curves::$name_affine) -> Self {
+                Option::from(value.coordinates())
+                    .map(|c: Coordinates<_>| {
+                        $name_affine::from_xy((*c.x()).into(), (*c.y()).into()).unwrap()
+                    })
+                    .unwrap_or_else($name_affine::identity)
+            }
+        }
+
+        impl From<$name_affine> for pasta_curves::$name_affine {
+            fn from(value: $name_affine) -> Self {
+                Option::from(value.coordinates())
+                    .map(|c: Coordinates<_>| {
+                        pasta_curves::$name_affine::from_xy((*c.x()).into(), (*c.y()).into())
+                            .unwrap()
+                    })
+                    .unwrap_or_else(pasta_curves::$name_affine::identity)
+            }
+        }
+    };
+}
+
+pasta_curves_conversions!(Ep, EpAffine);
+pasta_curves_conversions!(Eq, EqAffine);
+
+#[test]
+fn test_endo() {
+    use ff::Field;
+    use rand_core::OsRng;
+
+    for _ in 0..100000 {
+        let k = Fp::random(OsRng);
+        let (k1, k1_neg, k2, k2_neg) = Eq::decompose_scalar(&k);
+        let (k1_neg, k2_neg) = (bool::from(k1_neg), bool::from(k2_neg));
+        if k1_neg & k2_neg {
+            assert_eq!(k, -Fp::from_u128(k1) + Fp::ZETA * Fp::from_u128(k2))
+        } else if k1_neg {
+            assert_eq!(k, -Fp::from_u128(k1) - Fp::ZETA * Fp::from_u128(k2))
+        } else if k2_neg {
+            assert_eq!(k, Fp::from_u128(k1) + Fp::ZETA * Fp::from_u128(k2))
+        } else {
+            assert_eq!(k, Fp::from_u128(k1) - Fp::ZETA * Fp::from_u128(k2))
+        }
+    }
+
+    for _ in 0..100000 {
+        let k = Fq::random(OsRng);
+        let (k1, k1_neg, k2, k2_neg) = Ep::decompose_scalar(&k);
+        let (k1_neg, k2_neg) = (bool::from(k1_neg), bool::from(k2_neg));
+        if k1_neg & k2_neg {
+            assert_eq!(k, -Fq::from_u128(k1) + Fq::ZETA * Fq::from_u128(k2))
+        } else if k1_neg {
+            assert_eq!(k, -Fq::from_u128(k1) - Fq::ZETA * Fq::from_u128(k2))
+        } else if k2_neg {
+            assert_eq!(k, Fq::from_u128(k1) + Fq::ZETA * Fq::from_u128(k2))
+        } else {
+            assert_eq!(k, Fq::from_u128(k1) - Fq::ZETA * Fq::from_u128(k2))
+        }
+    }
+
+    crate::tests::curve::endo_decomposition_test::<Ep>();
+    crate::tests::curve::endo_decomposition_test::<Eq>();
+}
+
+#[test]
+fn test_glv_mul() {
+    crate::tests::curve::glv_mul_test::<Ep>();
+    crate::tests::curve::glv_mul_test::<Eq>();
+}
+
+#[test]
+fn test_curve() {
+    crate::tests::curve::curve_tests::<Ep>();
+    crate::tests::curve::curve_tests::<Eq>();
+}
+
+#[test]
+fn test_serialization() {
+    crate::tests::curve::random_serialization_test::<Ep>();
+    crate::tests::curve::random_serialization_test::<Eq>();
+    #[cfg(feature = "derive_serde")]
+    crate::tests::curve::random_serde_test::<Ep>();
+    #[cfg(feature = "derive_serde")]
+    crate::tests::curve::random_serde_test::<Eq>();
+}
+
+#[test]
+fn test_hash_to_curve() {
+    use rand_core::{OsRng, RngCore};
+
+    crate::tests::curve::hash_to_curve_test::<Ep>();
+    crate::tests::curve::hash_to_curve_with_test::<Ep>("SSWU");
+    crate::tests::curve::hash_to_curve_test::<Eq>();
+    crate::tests::curve::hash_to_curve_with_test::<Eq>("SSWU");
+
+    // The native hashers agree with `pasta_curves`.
+    for prefix in ["", "halo2curves", "z.cash:test"] {
+        let (pallas, vesta) = (pallas_hash_to_curve(prefix), vesta_hash_to_curve(prefix));
+        let (ep, eq) = (
+            pasta_curves::Ep::hash_to_curve(prefix),
+            pasta_curves::Eq::hash_to_curve(prefix),
+        );
+        for len in [0, 1, 32, 100] {
+            let mut message = vec![0; len];
+            OsRng.fill_bytes(&mut message);
+            assert_eq!(pallas(&message), ep(&message).into());
+            assert_eq!(vesta(&message), eq(&message).into());
+        }
+    }
+}
+
+#[test]
+fn test_pasta_curves() {
+    use rand_core::OsRng;
+
+    assert_eq!(
+        EpAffine::generator(),
+        pasta_curves::EpAffine::generator().into()
+    );
+    assert_eq!(
+        EqAffine::generator(),
+        pasta_curves::EqAffine::generator().into()
+    );
+    assert_eq!(
+        EpAffine::identity(),
+        pasta_curves::EpAffine::identity().into()
+    );
+    assert_eq!(Eq::identity(), pasta_curves::Eq::identity().into());
+
+    for _ in 0..100 {
+        let (p, k) = (Ep::random(OsRng), Fq::random(OsRng));
+        let q = pasta_curves::Ep::from(p);
+        assert_eq!(Ep::from(q), p);
+        assert_eq!(Ep::from(q * pasta_curves::Fq::from(k)), p * k);
+        assert_eq!(Ep::from(q.endo()), p.endo());
+        assert_eq!(EpAffine::from(q.to_affine()), p.to_affine());
+
+        let (p, k) = (Eq::random(OsRng), Fp::random(OsRng));
+        let q = pasta_curves::Eq::from(p);
+        assert_eq!(Eq::from(q), p);
+        assert_eq!(Eq::from(q * pasta_curves::Fp::from(k)), p * k);
+        assert_eq!(Eq::from(q.endo()), p.endo());
+        assert_eq!(EqAffine::from(q.to_affine()), p.to_affine());
+    }
+}
+
+#[test]
+fn test_pasta_curves_encoding() {
+    use rand_core::OsRng;
+
+    assert_eq!(Ep::identity().to_bytes(), [0; 32]);
+    assert_eq!(
+        Ep::identity().to_bytes(),
+        pasta_curves::Ep::identity().to_bytes()
+    );
+    assert_eq!(
+        Eq::identity().to_bytes(),
+        pasta_curves::Eq::identity().to_bytes()
+    );
+
+    for _ in 0..100 {
+        let p = Ep::random(OsRng);
+        let bytes = pasta_curves::Ep::from(p).to_bytes();
+        assert_eq!(p.to_bytes(), bytes);
+        assert_eq!(p.to_affine().to_bytes(), bytes);
+        assert_eq!(Ep::from_bytes(&bytes).unwrap(), p);
+
+        let p = Eq::random(OsRng);
+        let bytes = pasta_curves::Eq::from(p).to_bytes();
+        assert_eq!(p.to_bytes(), bytes);
+        assert_eq!(p.to_affine().to_bytes(), bytes);
+        assert_eq!(Eq::from_bytes(&bytes).unwrap(), p);
+    }
+}
+
+#[test]
+fn test_encode_to_curve() {
+    crate::tests::curve::encode_to_curve_test::<Ep>();
+    crate::tests::curve::encode_to_curve_test::<Eq>();
+}
@@ -0,0 +1,420 @@
+#[cfg(feature = "asm")]
+use crate::bn256::assembly::field_arithmetic_asm;
+#[cfg(not(feature = "asm"))]
+use crate::{arithmetic::macx, field_arithmetic, field_specific};
+
+use crate::arithmetic::{adc, mac, sbb};
+use crate::ff::{FromUniformBytes, PrimeField, WithSmallOrderMulGroup};
+use crate::{
+    field_bits, field_common, impl_add_binop_specify_output, impl_binops_additive,
+    impl_binops_additive_specify_output, impl_binops_multiplicative,
+    impl_binops_multiplicative_mixed, impl_from_u64, impl_sub_binop_specify_output, impl_sum_prod,
+};
+use core::convert::TryInto;
+use core::fmt;
+use core::ops::{Add, Mul, Neg, Sub};
+use rand::RngCore;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+
+/// This represents an element of $\mathbb{F}_p$ where
+///
+/// `p = 0x40000000000000000000000000000000224698fc094cf91b992d30ed00000001`
+///
+/// is the base field of the Pallas curve and the scalar field of the Vesta curve.
+// The internal representation of this type is four 64-bit unsigned
+// integers in little-endian order. `Fp` values are always in
+// Montgomery form; i.e., Fp(a) = aR mod p, with R = 2^256.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Fp(pub(crate) [u64; 4]);
+
+#[cfg(feature = "derive_serde")]
+crate::serialize_deserialize_32_byte_primefield!(Fp);
+
+/// Constant representing the modulus
+/// p = 0x40000000000000000000000000000000224698fc094cf91b992d30ed00000001
+const MODULUS: Fp = Fp([
+    0x992d30ed00000001,
+    0x224698fc094cf91b,
+    0x0000000000000000,
+    0x4000000000000000,
+]);
+
+/// The modulus as u32 limbs.
+#[cfg(not(target_pointer_width = "64"))]
+const MODULUS_LIMBS_32: [u32; 8] = [
+    0x0000_0001,
+    0x992d_30ed,
+    0x094c_f91b,
+    0x2246_98fc,
+    0x0000_0000,
+    0x0000_0000,
+    0x0000_0000,
+    0x4000_0000,
+];
+
+///Constant representing the modulus as static str
+const MODULUS_STR: &str = "0x40000000000000000000000000000000224698fc094cf91b992d30ed00000001";
+
+/// INV = -(p^{-1} mod 2^64) mod 2^64
+const INV: u64 = 0x992d30ecffffffff;
+
+/// R = 2^256 mod p
+/// 0x3fffffffffffffffffffffffffffffff992c350be41914ad34786d38fffffffd
+const R: Fp = Fp([
+    0x34786d38fffffffd,
+    0x992c350be41914ad,
+    0xffffffffffffffff,
+    0x3fffffffffffffff,
+]);
+
+/// R^2 = 2^512 mod p
+/// 0x96d41af7b9cb7147797a99bc3c95d18d7d30dbd8b0de0e78c78ecb30000000f
+const R2: Fp = Fp([
+    0x8c78ecb30000000f,
+    0xd7d30dbd8b0de0e7,
+    0x7797a99bc3c95d18,
+    0x096d41af7b9cb714,
+]);
+
+/// R^3 = 2^768 mod p
+/// 0x2ae309222d2d9910df8d1014353fd42cf6a68f3b6ac5b1d1f185a5993a9e10f9
+const R3: Fp = Fp([
+    0xf185a5993a9e10f9,
+    0xf6a68f3b6ac5b1d1,
+    0xdf8d1014353fd42c,
+    0x2ae309222d2d9910,
+]);
+
+/// `GENERATOR = 5 mod p` is a generator of the `p - 1` order multiplicative
+/// subgroup, or in other words a primitive root of the field.
+const GENERATOR: Fp = Fp::from_raw([0x05, 0x00, 0x00, 0x00]);
+
+/// GENERATOR^t where t * 2^s + 1 = p with t odd. In other words, this is a 2^s root of unity.
+/// `0x2bce74deac30ebda362120830561f81aea322bf2b7bb7584bdad6fabd87ea32f`
+const ROOT_OF_UNITY: Fp = Fp::from_raw([
+    0xbdad6fabd87ea32f,
+    0xea322bf2b7bb7584,
+    0x362120830561f81a,
+    0x2bce74deac30ebda,
+]);
+
+/// 1 / ROOT_OF_UNITY mod p
+const ROOT_OF_UNITY_INV: Fp = Fp::from_raw([
+    0xf0b87c7db2ce91f6,
+    0x84a0a1d8859f066f,
+    0xb4ed8e647196dad1,
+    0x2cd5282c53116b5c,
+]);
+
+/// 1 / 2 mod p
+const TWO_INV: Fp = Fp::from_raw([
+    0xcc96987680000001,
+    0x11234c7e04a67c8d,
+    0x0000000000000000,
+    0x2000000000000000,
+]);
+
+/// `ZETA^3 = 1 mod p` where `ZETA^2 != 1 mod p`, as in `pasta_curves`.
+const ZETA: Fp = Fp::from_raw([
+    0x1dad5ebdfdfe4ab9,
+    0x1d1f8bd237ad3149,
+    0x2caad5dc57aab1b0,
+    0x12ccca834acdba71,
+]);
+
+/// Generator of the t-order multiplicative subgroup.
+/// Computed by exponentiating Self::MULTIPLICATIVE_GENERATOR by 2^s, where s is Self::S.
+const DELTA: Fp = Fp::from_raw([
+    0x6a6ccd20dd7b9ba2,
+    0xf5e4f3f13eee5636,
+    0xbd455b7112a5049d,
+    0x0a757d0f0006ab6c,
+]);
+
+impl_binops_additive!(Fp, Fp);
+impl_binops_multiplicative!(Fp, Fp);
+field_common!(
+    Fp,
+    MODULUS,
+    INV,
+    MODULUS_STR,
+    TWO_INV,
+    ROOT_OF_UNITY_INV,
+    DELTA,
+    ZETA,
+    R,
+    R2,
+    R3
+);
+impl_from_u64!(Fp, R2);
+impl_sum_prod!(Fp);
+
+#[cfg(not(feature = "asm"))]
+field_arithmetic!(Fp, MODULUS, INV, sparse);
+#[cfg(feature = "asm")]
+field_arithmetic_asm!(Fp, MODULUS, INV);
+
+#[cfg(target_pointer_width = "64")]
+field_bits!(Fp, MODULUS);
+#[cfg(not(target_pointer_width = "64"))]
+field_bits!(Fp, MODULUS, MODULUS_LIMBS_32);
+
+impl Fp {
+    pub const fn size() -> usize {
+        32
+    }
+}
+
+impl ff::Field for Fp {
+    const ZERO: Self = Self::zero();
+    const ONE: Self = Self::one();
+
+    fn random(mut rng: impl RngCore) -> Self {
+        Self::from_u512([
+            rng.next_u64(),
+            rng.next_u64(),
+            rng.next_u64(),
+            rng.next_u64(),
+            rng.next_u64(),
+            rng.next_u64(),
+            rng.next_u64(),
+            rng.next_u64(),
+        ])
+    }
+
+    fn double(&self) -> Self {
+        self.double()
+    }
+
+    #[inline(always)]
+    fn square(&self) -> Self {
+        self.square()
+    }
+
+    /// Computes the multiplicative inverse of this element,
+    /// failing if the element is zero.
+    fn invert(&self) -> CtOption<Self> {
+        let tmp = self.pow_vartime([
+            0x992d30ecffffffff,
+            0x224698fc094cf91b,
+            0x0000000000000000,
+            0x4000000000000000,
+        ]);
+
+        CtOption::new(tmp, !self.ct_eq(&Self::zero()))
+    }
+
+    fn pow_vartime<S: AsRef<[u64]>>(&self, exp: S) -> Self {
+        let mut res = Self::one();
+        let mut found_one = false;
+        for e in exp.as_ref().iter().rev() {
+            for i in (0..64).rev() {
+                if found_one {
+                    res = res.square();
+                }
+
+                if ((*e >> i) & 1) == 1 {
+                    found_one = true;
+                    res *= self;
+                }
+            }
+        }
+        res
+    }
+
+    fn sqrt(&self) -> CtOption<Self> {
+        let tm1d2 = [
+            0x04a67c8dcc969876,
+            0x0000000011234c7e,
+            0x0000000000000000,
+            0x0000000020000000,
+        ];
+
+        ff::helpers::sqrt_tonelli_shanks(self, tm1d2)
+    }
+
+    fn sqrt_ratio(num: &Self, div: &Self) -> (Choice, Self) {
+        ff::helpers::sqrt_ratio_generic(num, div)
+    }
+}
+
+impl ff::PrimeField for Fp {
+    type Repr = [u8; 32];
+
+    const NUM_BITS: u32 = 255;
+    const CAPACITY: u32 = 254;
+    const MODULUS: &'static str = MODULUS_STR;
+    const MULTIPLICATIVE_GENERATOR: Self = GENERATOR;
+    const ROOT_OF_UNITY: Self = ROOT_OF_UNITY;
+    const ROOT_OF_UNITY_INV: Self = ROOT_OF_UNITY_INV;
+    const TWO_INV: Self = TWO_INV;
+    const DELTA: Self = DELTA;
+    const S: u32 = 32;
+
+    fn from_repr(repr: Self::Repr) -> CtOption<Self> {
+        let mut tmp = Fp([0, 0, 0, 0]);
+
+        tmp.0[0] = u64::from_le_bytes(repr[0..8].try_into().unwrap());
+        tmp.0[1] = u64::from_le_bytes(repr[8..16].try_into().unwrap());
+        tmp.0[2] = u64::from_le_bytes(repr[16..24].try_into().unwrap());
+        tmp.0[3] = u64::from_le_bytes(repr[24..32].try_into().unwrap());
+
+        // Try to subtract the modulus
+        let (_, borrow) = sbb(tmp.0[0], MODULUS.0[0], 0);
+        let (_, borrow) = sbb(tmp.0[1], MODULUS.0[1], borrow);
+        let (_, borrow) = sbb(tmp.0[2], MODULUS.0[2], borrow);
+        let (_, borrow) = sbb(tmp.0[3], MODULUS.0[3], borrow);
+
+        // If the element is smaller than MODULUS then the
+        // subtraction will underflow, producing a borrow value
+        // of 0xffff...ffff. Otherwise, it'll be zero.
+        let is_some = (borrow as u8) & 1;
+
+        // Convert to Montgomery form by computing
+        // (a.R^0 * R^2) / R = a.R
+        tmp *= &R2;
+
+        CtOption::new(tmp, Choice::from(is_some))
+    }
+
+    fn to_repr(&self) -> Self::Repr {
+        let tmp: [u64; 4] = (*self).into();
+        let mut res = [0; 32];
+        res[0..8].copy_from_slice(&tmp[0].to_le_bytes());
+        res[8..16].copy_from_slice(&tmp[1].to_le_bytes());
+        res[16..24].copy_from_slice(&tmp[2].to_le_bytes());
+        res[24..32].copy_from_slice(&tmp[3].to_le_bytes());
+
+        res
+    }
+
+    fn is_odd(&self) -> Choice {
+        Choice::from(self.to_repr()[0] & 1)
+    }
+}
+
+impl FromUniformBytes<64> for Fp {
+    /// Converts a 512-bit little endian integer into
+    /// an `Fp` by reducing by the modulus.
+    fn from_uniform_bytes(bytes: &[u8; 64]) -> Self {
+        Self::from_u512([
+            u64::from_le_bytes(bytes[0..8].try_into().unwrap()),
+            u64::from_le_bytes(bytes[8..16].try_into().unwrap()),
+            u64::from_le_bytes(bytes[16..24].try_into().unwrap()),
+            u64::from_le_bytes(bytes[24..32].try_into().unwrap()),
+            u64::from_le_bytes(bytes[32..40].try_into().unwrap()),
+            u64::from_le_bytes(bytes[40..48].try_into().unwrap()),
+            u64::from_le_bytes(bytes[48..56].try_into().unwrap()),
+            u64::from_le_bytes(bytes[56..64].try_into().unwrap()),
+        ])
+    }
+}
+
+impl WithSmallOrderMulGroup<3> for Fp {
+    const ZETA: Self = ZETA;
+}
+
+prime_field_legendre!(Fp);
+
+impl From<pasta_curves::Fp> for Fp {
+    fn from(value: pasta_curves::Fp) -> Self {
+        Fp::from_repr(value.to_repr()).unwrap()
+    }
+}
+
+impl From<Fp> for pasta_curves::Fp {
+    fn from(value: Fp) -> Self {
+        pasta_curves::Fp::from_repr(value.to_repr()).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ff::Field;
+    use rand_core::OsRng;
+
+    #[test]
+    fn test_sqrt() {
+        // NB: TWO_INV is standing in as a "random" field element
+        let v = (Fp::TWO_INV).square().sqrt().unwrap();
+        assert!(v == Fp::TWO_INV || (-v) == Fp::TWO_INV);
+
+        for _ in 0..10000 {
+            let a = Fp::random(OsRng);
+            let mut b = a;
+            b = b.square();
+
+            let b = b.sqrt().unwrap();
+            let mut negb = b;
+            negb = negb.neg();
+
+            assert!(a == b || a == negb);
+        }
+    }
+
+    #[test]
+    fn test_constants() {
+        assert_eq!(
+            Fp::MODULUS,
+            "0x40000000000000000000000000000000224698fc094cf91b992d30ed00000001",
+        );
+
+        assert_eq!(Fp::from(2) * Fp::TWO_INV, Fp::ONE);
+    }
+
+    #[test]
+    fn test_delta() {
+        assert_eq!(Fp::DELTA, Fp::MULTIPLICATIVE_GENERATOR.pow([1u64 << Fp::S]));
+    }
+
+    #[test]
+    fn test_root_of_unity() {
+        assert_eq!(Fp::ROOT_OF_UNITY.pow_vartime([1 << Fp::S]), Fp::one());
+    }
+
+    #[test]
+    fn test_inv_root_of_unity() {
+        assert_eq!(Fp::ROOT_OF_UNITY_INV, Fp::ROOT_OF_UNITY.invert().unwrap());
+    }
+
+    #[test]
+    fn test_field() {
+        crate::tests::field::random_field_tests::<Fp>("pallas base".to_string());
+    }
+
+    #[test]
+    fn test_conversion() {
+        crate::tests::field::random_conversion_tests::<Fp>("pallas base".to_string());
+    }
+
+    #[test]
+    #[cfg(feature = "bits")]
+    fn test_bits() {
+        crate::tests::field::random_bits_tests::<Fp>("pallas base".to_string());
+    }
+
+    #[test]
+    fn test_serialization() {
+        crate::tests::field::random_serialization_test::<Fp>("pallas base".to_string());
+        #[cfg(feature = "derive_serde")]
+        crate::tests::field::random_serde_test::<Fp>("pallas base".to_string());
+    }
+
+    #[test]
+    fn test_pasta_curves() {
+        // The constants and the arithmetic agree with `pasta_curves`.
+        assert_eq!(Fp::ROOT_OF_UNITY, pasta_curves::Fp::ROOT_OF_UNITY.into());
+        assert_eq!(Fp::DELTA, pasta_curves::Fp::DELTA.into());
+        assert_eq!(Fp::ZETA, pasta_curves::Fp::ZETA.into());
+
+        for _ in 0..1000 {
+            let (a, b) = (Fp::random(OsRng), Fp::random(OsRng));
+            let (c, d) = (pasta_curves::Fp::from(a), pasta_curves::Fp::from(b));
+            assert_eq!(Fp::from(c), a);
+            assert_eq!(Fp::from(c * d), a * b);
+            assert_eq!(Fp::from(c + d), a + b);
+            assert_eq!(Fp::from(c.invert().unwrap()), a.invert().unwrap());
+        }
+    }
+}
@@ -0,0 +1,420 @@
+#[cfg(feature = "asm")]
+use crate::bn256::assembly::field_arithmetic_asm;
+#[cfg(not(feature = "asm"))]
+use crate::{arithmetic::macx, field_arithmetic, field_specific};
+
+use crate::arithmetic::{adc, mac, sbb};
+use crate::ff::{FromUniformBytes, PrimeField, WithSmallOrderMulGroup};
+use crate::{
+    field_bits, field_common, impl_add_binop_specify_output, impl_binops_additive,
+    impl_binops_additive_specify_output, impl_binops_multiplicative,
+    impl_binops_multiplicative_mixed, impl_from_u64, impl_sub_binop_specify_output, impl_sum_prod,
+};
+use core::convert::TryInto;
+use core::fmt;
+use core::ops::{Add, Mul, Neg, Sub};
+use rand::RngCore;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+
+/// This represents an element of $\mathbb{F}_q$ where
+///
+/// `q = 0x40000000000000000000000000000000224698fc0994a8dd8c46eb2100000001`
+///
+/// is the base field of the Vesta curve and the scalar field of the Pallas curve.
+// The internal representation of this type is four 64-bit unsigned
+// integers in little-endian order. `Fq` values are always in
+// Montgomery form; i.e., Fq(a) = aR mod q, with R = 2^256.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Fq(pub(crate) [u64; 4]);
+
+#[cfg(feature = "derive_serde")]
+crate::serialize_deserialize_32_byte_primefield!(Fq);
+
+/// Constant representing the modulus
+/// q = 0x40000000000000000000000000000000224698fc0994a8dd8c46eb2100000001
+const MODULUS: Fq = Fq([
+    0x8c46eb2100000001,
+    0x224698fc0994a8dd,
+    0x0000000000000000,
+    0x4000000000000000,
+]);
+
+/// The modulus as u32 limbs.
+#[cfg(not(target_pointer_width = "64"))]
+const MODULUS_LIMBS_32: [u32; 8] = [
+    0x0000_0001,
+    0x8c46_eb21,
+    0x0994_a8dd,
+    0x2246_98fc,
+    0x0000_0000,
+    0x0000_0000,
+    0x0000_0000,
+    0x4000_0000,
+];
+
+///Constant representing the modulus as static str
+const MODULUS_STR: &str = "0x40000000000000000000000000000000224698fc0994a8dd8c46eb2100000001";
+
+/// INV = -(q^{-1} mod 2^64) mod 2^64
+const INV: u64 = 0x8c46eb20ffffffff;
+
+/// R = 2^256 mod q
+/// 0x3fffffffffffffffffffffffffffffff992c350be34205675b2b3e9cfffffffd
+const R: Fq = Fq([
+    0x5b2b3e9cfffffffd,
+    0x992c350be3420567,
+    0xffffffffffffffff,
+    0x3fffffffffffffff,
+]);
+
+/// R^2 = 2^512 mod q
+/// 0x96d41af7ccfdaa97fae231004ccf59067bb433d891a16e3fc9678ff0000000f
+const R2: Fq = Fq([
+    0xfc9678ff0000000f,
+    0x67bb433d891a16e3,
+    0x7fae231004ccf590,
+    0x096d41af7ccfdaa9,
+]);
+
+/// R^3 = 2^768 mod q
+/// 0x7dd97a06e6792c888fececb8e15cb63e13bda50dba41326008b421c249dae4c
+const R3: Fq = Fq([
+    0x008b421c249dae4c,
+    0xe13bda50dba41326,
+    0x88fececb8e15cb63,
+    0x07dd97a06e6792c8,
+]);
+
+/// `GENERATOR = 5 mod q` is a generator of the `q - 1` order multiplicative
+/// subgroup, or in other words a primitive root of the field.
+const GENERATOR: Fq = Fq::from_raw([0x05, 0x00, 0x00, 0x00]);
+
+/// GENERATOR^t where t * 2^s + 1 = q with t odd. In other words, this is a 2^s root of unity.
+/// `0x2de6a9b8746d3f589e5c4dfd492ae26e9bb97ea3c106f049a70e2c1102b6d05f`
+const ROOT_OF_UNITY: Fq = Fq::from_raw([
+    0xa70e2c1102b6d05f,
+    0x9bb97ea3c106f049,
+    0x9e5c4dfd492ae26e,
+    0x2de6a9b8746d3f58,
+]);
+
+/// 1 / ROOT_OF_UNITY mod q
+const ROOT_OF_UNITY_INV: Fq = Fq::from_raw([
+    0x57eecda0a84b6836,
+    0x4ad38b9084b8a80c,
+    0xf4c8f353124086c1,
+    0x2235e1a7415bf936,
+]);
+
+/// 1 / 2 mod q
+const TWO_INV: Fq = Fq::from_raw([
+    0xc623759080000001,
+    0x11234c7e04ca546e,
+    0x0000000000000000,
+    0x2000000000000000,
+]);
+
+/// `ZETA^3 = 1 mod q` where `ZETA^2 != 1 mod q`, as in `pasta_curves`.
+const ZETA: Fq = Fq::from_raw([
+    0x2aa9d2e050aa0e4f,
+    0x0fed467d47c033af,
+    0x511db4d81cf70f5a,
+    0x06819a58283e528e,
+]);
+
+/// Generator of the t-order multiplicative subgroup.
+/// Computed by exponentiating Self::MULTIPLICATIVE_GENERATOR by 2^s, where s is Self::S.
+const DELTA: Fq = Fq::from_raw([
+    0x8494392472d1683c,
+    0xe3ac3376541d1140,
+    0x06f0a88e7f7949f8,
+    0x2237d54423724166,
+]);
+
+impl_binops_additive!(Fq, Fq);
+impl_binops_multiplicative!(Fq, Fq);
+field_common!(
+    Fq,
+    MODULUS,
+    INV,
+    MODULUS_STR,
+    TWO_INV,
+    ROOT_OF_UNITY_INV,
+    DELTA,
+    ZETA,
+    R,
+    R2,
+    R3
+);
+impl_from_u64!(Fq, R2);
+impl_sum_prod!(Fq);
+
+#[cfg(not(feature = "asm"))]
+field_arithmetic!(Fq, MODULUS, INV, sparse);
+#[cfg(feature = "asm")]
+field_arithmetic_asm!(Fq, MODULUS, INV);
+
+#[cfg(target_pointer_width = "64")]
+field_bits!(Fq, MODULUS);
+#[cfg(not(target_pointer_width = "64"))]
+field_bits!(Fq, MODULUS, MODULUS_LIMBS_32);
+
+impl Fq {
+    pub const fn size() -> usize {
+        32
+    }
+}
+
+impl ff::Field for Fq {
+    const ZERO: Self = Self::zero();
+    const ONE: Self = Self::one();
+
+    fn random(mut rng: impl RngCore) -> Self {
+        Self::from_u512([
+            rng.next_u64(),
+            rng.next_u64(),
+            rng.next_u64(),
+            rng.next_u64(),
+            rng.next_u64(),
+            rng.next_u64(),
+            rng.next_u64(),
+            rng.next_u64(),
+        ])
+    }
+
+    fn double(&self) -> Self {
+        self.double()
+    }
+
+    #[inline(always)]
+    fn square(&self) -> Self {
+        self.square()
+    }
+
+    /// Computes the multiplicative inverse of this element,
+    /// failing if the element is zero.
+    fn invert(&self) -> CtOption<Self> {
+        let tmp = self.pow_vartime([
+            0x8c46eb20ffffffff,
+            0x224698fc0994a8dd,
+            0x0000000000000000,
+            0x4000000000000000,
+        ]);
+
+        CtOption::new(tmp, !self.ct_eq(&Self::zero()))
+    }
+
+    fn pow_vartime<S: AsRef<[u64]>>(&self, exp: S) -> Self {
+        let mut res = Self::one();
+        let mut found_one = false;
+        for e in exp.as_ref().iter().rev() {
+            for i in (0..64).rev() {
+                if found_one {
+                    res = res.square();
+                }
+
+                if ((*e >> i) & 1) == 1 {
+                    found_one = true;
+                    res *= self;
+                }
+            }
+        }
+        res
+    }
+
+    fn sqrt(&self) -> CtOption<Self> {
+        let tm1d2 = [
+            0x04ca546ec6237590,
+            0x0000000011234c7e,
+            0x0000000000000000,
+            0x0000000020000000,
+        ];
+
+        ff::helpers::sqrt_tonelli_shanks(self, tm1d2)
+    }
+
+    fn sqrt_ratio(num: &Self, div: &Self) -> (Choice, Self) {
+        ff::helpers::sqrt_ratio_generic(num, div)
+    }
+}
+
+impl ff::PrimeField for Fq {
+    type Repr = [u8; 32];
+
+    const NUM_BITS: u32 = 255;
+    const CAPACITY: u32 = 254;
+    const MODULUS: &'static str = MODULUS_STR;
+    const MULTIPLICATIVE_GENERATOR: Self = GENERATOR;
+    const ROOT_OF_UNITY: Self = ROOT_OF_UNITY;
+    const ROOT_OF_UNITY_INV: Self = ROOT_OF_UNITY_INV;
+    const TWO_INV: Self = TWO_INV;
+    const DELTA: Self = DELTA;
+    const S: u32 = 32;
+
+    fn from_repr(repr: Self::Repr) -> CtOption<Self> {
+        let mut tmp = Fq([0, 0, 0, 0]);
+
+        tmp.0[0] = u64::from_le_bytes(repr[0..8].try_into().unwrap());
+        tmp.0[1] = u64::from_le_bytes(repr[8..16].try_into().unwrap());
+        tmp.0[2] = u64::from_le_bytes(repr[16..24].try_into().unwrap());
+        tmp.0[3] = u64::from_le_bytes(repr[24..32].try_into().unwrap());
+
+        // Try to subtract the modulus
+        let (_, borrow) = sbb(tmp.0[0], MODULUS.0[0], 0);
+        let (_, borrow) = sbb(tmp.0[1], MODULUS.0[1], borrow);
+        let (_, borrow) = sbb(tmp.0[2], MODULUS.0[2], borrow);
+        let (_, borrow) = sbb(tmp.0[3], MODULUS.0[3], borrow);
+
+        // If the element is smaller than MODULUS then the
+        // subtraction will underflow, producing a borrow value
+        // of 0xffff...ffff. Otherwise, it'll be zero.
+        let is_some = (borrow as u8) & 1;
+
+        // Convert to Montgomery form by computing
+        // (a.R^0 * R^2) / R = a.R
+        tmp *= &R2;
+
+        CtOption::new(tmp, Choice::from(is_some))
+    }
+
+    fn to_repr(&self) -> Self::Repr {
+        let tmp: [u64; 4] = (*self).into();
+        let mut res = [0; 32];
+        res[0..8].copy_from_slice(&tmp[0].to_le_bytes());
+        res[8..16].copy_from_slice(&tmp[1].to_le_bytes());
+        res[16..24].copy_from_slice(&tmp[2].to_le_bytes());
+        res[24..32].copy_from_slice(&tmp[3].to_le_bytes());
+
+        res
+    }
+
+    fn is_odd(&self) -> Choice {
+        Choice::from(self.to_repr()[0] & 1)
+    }
+}
+
+impl FromUniformBytes<64> for Fq {
+    /// Converts a 512-bit little endian integer into
+    /// an `Fq` by reducing by the modulus.
+    fn from_uniform_bytes(bytes: &[u8; 64]) -> Self {
+        Self::from_u512([
+            u64::from_le_bytes(bytes[0..8].try_into().unwrap()),
+            u64::from_le_bytes(bytes[8..16].try_into().unwrap()),
+            u64::from_le_bytes(bytes[16..24].try_into().unwrap()),
+            u64::from_le_bytes(bytes[24..32].try_into().unwrap()),
+            u64::from_le_bytes(bytes[32..40].try_into().unwrap()),
+            u64::from_le_bytes(bytes[40..48].try_into().unwrap()),
+            u64::from_le_bytes(bytes[48..56].try_into().unwrap()),
+            u64::from_le_bytes(bytes[56..64].try_into().unwrap()),
+        ])
+    }
+}
+
+impl WithSmallOrderMulGroup<3> for Fq {
+    const ZETA: Self = ZETA;
+}
+
+prime_field_legendre!(Fq);
+
+impl From<pasta_curves::Fq> for Fq {
+    fn from(value: pasta_curves::Fq) -> Self {
+        Fq::from_repr(value.to_repr()).unwrap()
+    }
+}
+
+impl From<Fq> for pasta_curves::Fq {
+    fn from(value: Fq) -> Self {
+        pasta_curves::Fq::from_repr(value.to_repr()).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ff::Field;
+    use rand_core::OsRng;
+
+    #[test]
+    fn test_sqrt() {
+        // NB: TWO_INV is standing in as a "random" field element
+        let v = (Fq::TWO_INV).square().sqrt().unwrap();
+        assert!(v == Fq::TWO_INV || (-v) == Fq::TWO_INV);
+
+        for _ in 0..10000 {
+            let a = Fq::random(OsRng);
+            let mut b = a;
+            b = b.square();
+
+            let b = b.sqrt().unwrap();
+            let mut negb = b;
+            negb = negb.neg();
+
+            assert!(a == b || a == negb);
+        }
+    }
+
+    #[test]
+    fn test_constants() {
+        assert_eq!(
+            Fq::MODULUS,
+            "0x40000000000000000000000000000000224698fc0994a8dd8c46eb2100000001",
+        );
+
+        assert_eq!(Fq::from(2) * Fq::TWO_INV, Fq::ONE);
+    }
+
+    #[test]
+    fn test_delta() {
+        assert_eq!(Fq::DELTA, Fq::MULTIPLICATIVE_GENERATOR.pow([1u64 << Fq::S]));
+    }
+
+    #[test]
+    fn test_root_of_unity() {
+        assert_eq!(Fq::ROOT_OF_UNITY.pow_vartime([1 << Fq::S]), Fq::one());
+    }
+
+    #[test]
+    fn test_inv_root_of_unity() {
+        assert_eq!(Fq::ROOT_OF_UNITY_INV, Fq::ROOT_OF_UNITY.invert().unwrap());
+    }
+
+    #[test]
+    fn test_field() {
+        crate::tests::field::random_field_tests::<Fq>("vesta base".to_string());
+    }
+
+    #[test]
+    fn test_conversion() {
+        crate::tests::field::random_conversion_tests::<Fq>("vesta base".to_string());
+    }
+
+    #[test]
+    #[cfg(feature = "bits")]
+    fn test_bits() {
+        crate::tests::field::random_bits_tests::<Fq>("vesta base".to_string());
+    }
+
+    #[test]
+    fn test_serialization() {
+        crate::tests::field::random_serialization_test::<Fq>("vesta base".to_string());
+        #[cfg(feature = "derive_serde")]
+        crate::tests::field::random_serde_test::<Fq>("vesta base".to_string());
+    }
+
+    #[test]
+    fn test_pasta_curves() {
+        // The constants and the arithmetic agree with `pasta_curves`.
+        assert_eq!(Fq::ROOT_OF_UNITY, pasta_curves::Fq::ROOT_OF_UNITY.into());
+        assert_eq!(Fq::DELTA, pasta_curves::Fq::DELTA.into());
+        assert_eq!(Fq::ZETA, pasta_curves::Fq::ZETA.into());
+
+        for _ in 0..1000 {
+            let (a, b) = (Fq::random(OsRng), Fq::random(OsRng));
+            let (c, d) = (pasta_curves::Fq::from(a), pasta_curves::Fq::from(b));
+            assert_eq!(Fq::from(c), a);
+            assert_eq!(Fq::from(c * d), a * b);
+            assert_eq!(Fq::from(c + d), a + b);
+            assert_eq!(Fq::from(c.invert().unwrap()), a.invert().unwrap());
+        }
+    }
+}
@@ -1,7 +1,14 @@
+use crate::arithmetic::{mul_512, sbb, CurveEndo, EndoParameters};
+use crate::endo;
 use crate::ff::WithSmallOrderMulGroup;
 use crate::ff::{Field, PrimeField};
 use crate::group::{prime::PrimeCurveAffine, Curve, Group as _, GroupEncoding};
-use crate::hash_to_curve::svdw_hash_to_curve;
+use crate::hash_to_curve::{
+    simple_svdw_isogeny_map_to_curve, svdw_encode_to_curve, svdw_hash_to_curve,
+    svdw_hash_to_curve_with, svdw_map_to_curve, svdw_map_to_curve_inverse,
+    svdw_precomputed_constants, ElligatorSquared, EncodeToCurve, ExpandMessage, ExpandMsgXmd,
+    HashToCurveWith, Isogeny, Suite,
+};
 use crate::secp256k1::Fp;
 use crate::secp256k1::Fq;
 use crate::{Coordinates, CurveAffine, CurveExt};
@@ -70,8 +77,191 @@ new_curve_impl!(
 
 impl Secp256k1 {
     const SVDW_Z: Fp = Fp::ONE;
+
+    // Z = -11 (reference: <https://www.rfc-editor.org/rfc/rfc9380.html#section-8.7>)
+    const SSWU_Z: Fp = Fp::from_raw([
+        0xfffffffefffffc24,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+    ]);
+}
+
+impl EncodeToCurve for Secp256k1 {
+    fn encode_to_curve<'a>(domain_prefix: &'a str) -> Box<dyn Fn(&[u8]) -> Self + 'a> {
+        svdw_encode_to_curve(Self::CURVE_ID, domain_prefix, Secp256k1::SVDW_Z)
+    }
+}
+
+impl HashToCurveWith for Secp256k1 {
+    fn hash_to_curve_with<'a, X: ExpandMessage + 'a>(
+        dst: &[u8],
+    ) -> Box<dyn Fn(&[u8]) -> Self + 'a> {
+        svdw_hash_to_curve_with::<Secp256k1, X, 64>(dst.to_vec(), Secp256k1::SVDW_Z)
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref SVDW_CONSTANTS: [Fp; 4] = svdw_precomputed_constants::<Secp256k1>(Secp256k1::SVDW_Z);
+}
+
+/// Elligator Squared over the SVDW map of [`CurveExt::hash_to_curve`]; the
+/// isogeny of the SSWU map is not inverted.
+impl ElligatorSquared for Secp256k1 {
+    fn map_to_curve(u: Fp) -> CtOption<Secp256k1> {
+        let [c1, c2, c3, c4] = *SVDW_CONSTANTS;
+        svdw_map_to_curve(u, c1, c2, c3, c4, Secp256k1::SVDW_Z)
+    }
+
+    fn map_to_curve_inverse(&self) -> [CtOption<Fp>; 8] {
+        let [c1, c2, c3, c4] = *SVDW_CONSTANTS;
+        svdw_map_to_curve_inverse(self, c1, c2, c3, c4, Secp256k1::SVDW_Z)
+    }
+}
+
+// The 3-isogeny from `y^2 = x^3 + A' * x + B'` to secp256k1, see
+// <https://www.rfc-editor.org/rfc/rfc9380.html#appendix-E.1>.
+const ISO_A: Fp = Fp::from_raw([
+    0x405447c01a444533,
+    0xe953d363cb6f0e5d,
+    0xa08a5558f0f5d272,
+    0x3f8731abdd661adc,
+]);
+const ISO_B: Fp = Fp::from_raw([1771, 0, 0, 0]);
+
+const ISO_X_NUM: [Fp; 4] = [
+    Fp::from_raw([
+        0x8e38e38daaaaa8c7,
+        0x38e38e38e38e38e3,
+        0xe38e38e38e38e38e,
+        0x8e38e38e38e38e38,
+    ]),
+    Fp::from_raw([
+        0xdfff1044f17c6581,
+        0xd595d2fc0bf63b92,
+        0xb9f315cea7fd44c5,
+        0x07d3d4c80bc321d5,
+    ]),
+    Fp::from_raw([
+        0x4ecbd0b53d9dd262,
+        0xe4506144037c4031,
+        0xe2a413deca25caec,
+        0x534c328d23f234e6,
+    ]),
+    Fp::from_raw([
+        0x8e38e38daaaaa88c,
+        0x38e38e38e38e38e3,
+        0xe38e38e38e38e38e,
+        0x8e38e38e38e38e38,
+    ]),
+];
+
+const ISO_X_DEN: [Fp; 2] = [
+    Fp::from_raw([
+        0x9fe6b745781eb49b,
+        0x86cd409542f8487d,
+        0x9ca34ccbb7b640dd,
+        0xd35771193d94918a,
+    ]),
+    Fp::from_raw([
+        0xc52a56612a8c6d14,
+        0x06d36b641f5e41bb,
+        0xf7c4b2d51b542254,
+        0xedadc6f64383dc1d,
+    ]),
+];
+
+const ISO_Y_NUM: [Fp; 4] = [
+    Fp::from_raw([
+        0xa12f684b8e38e23c,
+        0x2f684bda12f684bd,
+        0x684bda12f684bda1,
+        0x4bda12f684bda12f,
+    ]),
+    Fp::from_raw([
+        0xdffc90fc201d71a3,
+        0x647ab046d686da6f,
+        0xa9d0a54b12a0a6d5,
+        0xc75e0c32d5cb7c0f,
+    ]),
+    Fp::from_raw([
+        0xa765e85a9ecee931,
+        0x722830a201be2018,
+        0x715209ef6512e576,
+        0x29a6194691f91a73,
+    ]),
+    Fp::from_raw([
+        0x84bda12f38e38d84,
+        0xbda12f684bda12f6,
+        0xa12f684bda12f684,
+        0x2f684bda12f684bd,
+    ]),
+];
+
+const ISO_Y_DEN: [Fp; 3] = [
+    Fp::from_raw([
+        0xfffffffefffff93b,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+    ]),
+    Fp::from_raw([
+        0xdfb425d2685c2573,
+        0x9467c1bfc8e8d978,
+        0xd5e9e6632722c298,
+        0x7a06534bb8bdb49f,
+    ]),
+    Fp::from_raw([
+        0xa7bf8192bfd2a76f,
+        0x0a3d21162f0d6299,
+        0xf3a70c3fa8fe337e,
+        0x6484aa716545ca2c,
+    ]),
+];
+
+const ISOGENY: Isogeny<Fp> = Isogeny {
+    a: ISO_A,
+    b: ISO_B,
+    x_num: &ISO_X_NUM,
+    x_den: &ISO_X_DEN,
+    y_num: &ISO_Y_NUM,
+    y_den: &ISO_Y_DEN,
+};
+
+/// The `secp256k1_XMD:SHA-256_SSWU_RO_` and `secp256k1_XMD:SHA-256_SSWU_NU_`
+/// hash-to-curve suites of https://datatracker.ietf.org/doc/html/rfc9380#section-8.7, which map to a
+/// 3-isogenous curve with the simplified SWU map. Unlike it, the
+/// `hash_to_curve` of [`CurveExt`] uses the SVDW map.
+#[derive(Clone, Copy, Debug)]
+pub struct Secp256k1XmdSha256Sswu;
+
+impl Suite for Secp256k1XmdSha256Sswu {
+    type Curve = Secp256k1;
+    type Expander = ExpandMsgXmd<sha2::Sha256>;
+    const RO_ID: &'static str = "secp256k1_XMD:SHA-256_SSWU_RO_";
+    const NU_ID: &'static str = "secp256k1_XMD:SHA-256_SSWU_NU_";
+
+    fn map_to_curve(u: Fp) -> CtOption<Secp256k1> {
+        simple_svdw_isogeny_map_to_curve(u, Secp256k1::SSWU_Z, &ISOGENY)
+    }
 }
 
+// Short basis `(a1, -b1), (a2, b2)` of the lattice `{(x, y) : x + ZETA * y = 0 mod n}`
+// with `a1 * b2 + a2 * b1 = n`, where `ZETA` is `secp256k1::Fq::ZETA` and `n` the scalar field
+// modulus.
+// See https://github.com/demining/Endomorphism-Secp256k1/blob/main/README.md
+// to have more details about the endomorphism.
+const ENDO_PARAMS_SECP256K1: EndoParameters = EndoParameters {
+    // round(2^256 * b1 / n)
+    gamma1: [0x6f547fa90abfe4c4, 0xe4437ed6010e8828, 0x0, 0x0],
+    // round(2^256 * b2 / n)
+    gamma2: [0xe86c90e49284eb15, 0x3086d221a7d46bcd, 0x0, 0x0],
+    b1: [0x6f547fa90abfe4c3, 0xe4437ed6010e8828, 0x0, 0x0],
+    b2: [0xe86c90e49284eb15, 0x3086d221a7d46bcd, 0x0, 0x0],
+};
+
+endo!(Secp256k1, Fq, ENDO_PARAMS_SECP256K1);
+
 #[test]
 fn test_curve() {
     crate::tests::curve::curve_tests::<Secp256k1>();
@@ -80,6 +270,133 @@ fn test_curve() {
 #[test]
 fn test_hash_to_curve() {
     crate::tests::curve::hash_to_curve_test::<Secp256k1>();
+    crate::tests::curve::hash_to_curve_with_test::<Secp256k1>("SVDW");
+}
+
+#[test]
+fn test_encode_to_curve() {
+    crate::tests::curve::encode_to_curve_test::<Secp256k1>();
+}
+
+#[test]
+fn test_elligator_squared() {
+    crate::tests::curve::elligator_squared_test::<Secp256k1>();
+}
+
+#[test]
+fn test_map_to_curve_exceptional() {
+    use crate::tests::fe_from_str;
+
+    // `u = 0` and `u = sqrt(-1 / Z)`, for which `Z^2 * u^4 + Z * u^2 = 0`,
+    // computed with an independent implementation.
+    for (u, (x, y)) in [
+        (
+            "0x0",
+            (
+                "0xbf6ce2abc92f03c7abfb18752134acc036b8e8ef46a7ed2634a86727c12d6ac1",
+                "0xcb18d77a942ce3413cfb072b4f6c28b51ee64786e67fa94cf7b24de22d281a15",
+            ),
+        ),
+        (
+            "0x331716177ec001cf0b2a4b9bf5c63274440235ba3dc0af713237ec866179d785",
+            (
+                "0xbf6ce2abc92f03c7abfb18752134acc036b8e8ef46a7ed2634a86727c12d6ac1",
+                "0x34e728856bd31cbec304f8d4b093d74ae119b879198056b3084db21cd2d7e21a",
+            ),
+        ),
+    ] {
+        let expected = Secp256k1Affine::from_xy(fe_from_str(x), fe_from_str(y)).unwrap();
+        let output = Secp256k1XmdSha256Sswu::map_to_curve(fe_from_str(u)).unwrap();
+        assert_eq!(output.to_affine(), expected);
+    }
+}
+
+#[test]
+fn test_encode_to_curve_suite() {
+    use crate::tests::fe_from_str;
+
+    // Test vectors of https://datatracker.ietf.org/doc/html/rfc9380#appendix-J.8.2
+    let dst = format!("QUUX-V01-CS02-with-{}", Secp256k1XmdSha256Sswu::NU_ID);
+    for (message, (x, y)) in [
+        (
+            &b""[..],
+            (
+                "0xa4792346075feae77ac3b30026f99c1441b4ecf666ded19b7522cf65c4c55c5b",
+                "0x62c59e2a6aeed1b23be5883e833912b08ba06be7f57c0e9cdc663f31639ff3a7",
+            ),
+        ),
+        (
+            b"abc",
+            (
+                "0x3f3b5842033fff837d504bb4ce2a372bfeadbdbd84a1d2b678b6e1d7ee426b9d",
+                "0x902910d1fef15d8ae2006fc84f2a5a7bda0e0407dc913062c3a493c4f5d876a5",
+            ),
+        ),
+    ] {
+        let expected = Secp256k1Affine::from_xy(fe_from_str(x), fe_from_str(y)).unwrap();
+        let output = Secp256k1XmdSha256Sswu::encode_to_curve(message, dst.as_bytes());
+        assert_eq!(output.to_affine(), expected);
+    }
+}
+
+#[test]
+fn test_hash_to_curve_suite() {
+    use crate::tests::fe_from_str;
+
+    // Test vectors of https://datatracker.ietf.org/doc/html/rfc9380#appendix-J.8.1
+    let dst = format!("QUUX-V01-CS02-with-{}", Secp256k1XmdSha256Sswu::RO_ID);
+    for (message, (x, y)) in [
+        (
+            &b""[..],
+            (
+                "0xc1cae290e291aee617ebaef1be6d73861479c48b841eaba9b7b5852ddfeb1346",
+                "0x64fa678e07ae116126f08b022a94af6de15985c996c3a91b64c406a960e51067",
+            ),
+        ),
+        (
+            b"abc",
+            (
+                "0x3377e01eab42db296b512293120c6cee72b6ecf9f9205760bd9ff11fb3cb2c4b",
+                "0x7f95890f33efebd1044d382a01b1bee0900fb6116f94688d487c6c7b9c8371f6",
+            ),
+        ),
+    ] {
+        let expected = Secp256k1Affine::from_xy(fe_from_str(x), fe_from_str(y)).unwrap();
+        let output = Secp256k1XmdSha256Sswu::hash_to_curve(message, dst.as_bytes());
+        assert_eq!(output.to_affine(), expected);
+    }
+}
+
+#[test]
+fn test_isogeny() {
+    use rand_core::OsRng;
+
+    // Random point of the isogenous curve `y^2 = x^3 + A' * x + B'`.
+    let random_point = || loop {
+        let x = Fp::random(OsRng);
+        if let Some(y) = Option::from((x.square() * x + ISO_A * x + ISO_B).sqrt()) {
+            return (x, y);
+        }
+    };
+    let add = |(x1, y1): (Fp, Fp), (x2, y2): (Fp, Fp)| {
+        let lambda = (y2 - y1) * (x2 - x1).invert().unwrap();
+        let x3 = lambda.square() - x1 - x2;
+        (x3, lambda * (x1 - x3) - y1)
+    };
+
+    // The isogeny is a group homomorphism.
+    for _ in 0..10 {
+        let (p, q) = (random_point(), random_point());
+        let r = add(p, q);
+        let [p, q, r]: [Secp256k1; 3] = [p, q, r].map(|(x, y)| ISOGENY.map(x, y).unwrap());
+        assert!(bool::from(p.is_on_curve()));
+        assert_eq!(p + q, r);
+    }
+
+    for u in [Fp::ZERO, Fp::ONE, -Fp::ONE] {
+        let p = Secp256k1XmdSha256Sswu::map_to_curve(u).unwrap();
+        assert!(bool::from(p.is_on_curve()));
+    }
 }
 
 #[test]
@@ -95,6 +412,43 @@ fn test_endo_consistency() {
     assert_eq!(g * Fq::ZETA, g.endo());
 }
 
+#[test]
+fn test_endo() {
+    use rand_core::OsRng;
+
+    for _ in 0..100000 {
+        let k = Fq::random(OsRng);
+        let (k1, k1_neg, k2, k2_neg) = Secp256k1::decompose_scalar(&k);
+        let (k1_neg, k2_neg) = (bool::from(k1_neg), bool::from(k2_neg));
+        if k1_neg & k2_neg {
+            assert_eq!(k, -Fq::from_u128(k1) + Fq::ZETA * Fq::from_u128(k2))
+        } else if k1_neg {
+            assert_eq!(k, -Fq::from_u128(k1) - Fq::ZETA * Fq::from_u128(k2))
+        } else if k2_neg {
+            assert_eq!(k, Fq::from_u128(k1) + Fq::ZETA * Fq::from_u128(k2))
+        } else {
+            assert_eq!(k, Fq::from_u128(k1) - Fq::ZETA * Fq::from_u128(k2))
+        }
+    }
+
+    crate::tests::curve::endo_decomposition_test::<Secp256k1>();
+}
+
+#[test]
+fn test_mul_generator() {
+    use rand_core::OsRng;
+
+    for _ in 0..100 {
+        let s = Fq::random(OsRng);
+        assert_eq!(Secp256k1::mul_generator(&s), Secp256k1::generator() * s);
+    }
+}
+
+#[test]
+fn test_glv_mul() {
+    crate::tests::curve::glv_mul_test::<Secp256k1>();
+}
+
 #[test]
 fn ecdsa_example() {
     use crate::group::Curve;
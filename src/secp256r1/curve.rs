@@ -1,7 +1,10 @@
 use crate::ff::WithSmallOrderMulGroup;
 use crate::ff::{Field, PrimeField};
 use crate::group::{prime::PrimeCurveAffine, Curve, Group as _, GroupEncoding};
-use crate::hash_to_curve::simple_svdw_hash_to_curve;
+use crate::hash_to_curve::{
+    simple_svdw_encode_to_curve, simple_svdw_hash_to_curve, simple_svdw_hash_to_curve_with,
+    simple_svdw_map_to_curve, EncodeToCurve, ExpandMessage, ExpandMsgXmd, HashToCurveWith, Suite,
+};
 use crate::secp256r1::Fp;
 use crate::secp256r1::Fq;
 use crate::{Coordinates, CurveAffine, CurveExt};
@@ -91,17 +94,144 @@ impl Secp256r1 {
     ]);
 }
 
+impl EncodeToCurve for Secp256r1 {
+    fn encode_to_curve<'a>(domain_prefix: &'a str) -> Box<dyn Fn(&[u8]) -> Self + 'a> {
+        simple_svdw_encode_to_curve(Self::CURVE_ID, domain_prefix, Secp256r1::SSVDW_Z)
+    }
+}
+
+impl HashToCurveWith for Secp256r1 {
+    fn hash_to_curve_with<'a, X: ExpandMessage + 'a>(
+        dst: &[u8],
+    ) -> Box<dyn Fn(&[u8]) -> Self + 'a> {
+        simple_svdw_hash_to_curve_with::<Secp256r1, X>(dst.to_vec(), Secp256r1::SSVDW_Z)
+    }
+}
+
+/// The `P256_XMD:SHA-256_SSWU_RO_` and `P256_XMD:SHA-256_SSWU_NU_`
+/// hash-to-curve suites of https://datatracker.ietf.org/doc/html/rfc9380#section-8.2.
+#[derive(Clone, Copy, Debug)]
+pub struct P256XmdSha256Sswu;
+
+impl Suite for P256XmdSha256Sswu {
+    type Curve = Secp256r1;
+    type Expander = ExpandMsgXmd<sha2::Sha256>;
+    const RO_ID: &'static str = "P256_XMD:SHA-256_SSWU_RO_";
+    const NU_ID: &'static str = "P256_XMD:SHA-256_SSWU_NU_";
+
+    fn map_to_curve(u: Fp) -> CtOption<Secp256r1> {
+        simple_svdw_map_to_curve(u, Secp256r1::SSVDW_Z)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::group::Curve;
     use crate::secp256r1::{Fp, Fq, Secp256r1};
+    use crate::tests::fe_from_str;
     use ff::FromUniformBytes;
     use rand_core::OsRng;
 
     #[test]
     fn test_hash_to_curve() {
         crate::tests::curve::hash_to_curve_test::<Secp256r1>();
+        crate::tests::curve::hash_to_curve_with_test::<Secp256r1>("SSWU");
+    }
+
+    #[test]
+    fn test_map_to_curve_exceptional() {
+        // `u = 0` and `u = sqrt(-1 / Z)`, for which `Z^2 * u^4 + Z * u^2 = 0`,
+        // computed with an independent implementation.
+        for (u, (x, y)) in [
+            (
+                "0x0",
+                (
+                    "0xa528bd8696bdaf996c65b982d94959d3146fe6a020693090bdba13132375f224",
+                    "0x0e5fb73d16791ce358fb5adb2d33668a3b24099fd8d401f6685e0e994fb4d756",
+                ),
+            ),
+            (
+                "0x95d527d249c8dc5cadbf4c70bb59aaab72c14fffbad5622bd147b86a639ec6d9",
+                (
+                    "0xa528bd8696bdaf996c65b982d94959d3146fe6a020693090bdba13132375f224",
+                    "0xf1a048c1e986e31da704a524d2cc9975c4dbf661272bfe0997a1f166b04b28a9",
+                ),
+            ),
+        ] {
+            let expected = Secp256r1Affine::from_xy(fe_from_str(x), fe_from_str(y)).unwrap();
+            let output = P256XmdSha256Sswu::map_to_curve(fe_from_str(u)).unwrap();
+            assert_eq!(output.to_affine(), expected);
+        }
+
+        // With a square `Z` the map is invalid, but it neither panics nor
+        // returns points off the curve.
+        let outputs = (0..16u64)
+            .map(|u| simple_svdw_map_to_curve::<Secp256r1>(Fp::from(u), Fp::ONE))
+            .collect::<Vec<_>>();
+        assert!(outputs.iter().any(|p| bool::from(p.is_none())));
+        for p in outputs {
+            assert!(bool::from(
+                p.map(|p| p.is_on_curve()).unwrap_or(Choice::from(1))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_encode_to_curve() {
+        crate::tests::curve::encode_to_curve_test::<Secp256r1>();
+    }
+
+    #[test]
+    fn test_encode_to_curve_suite() {
+        // Test vectors of https://datatracker.ietf.org/doc/html/rfc9380#appendix-J.1.2
+        let dst = format!("QUUX-V01-CS02-with-{}", P256XmdSha256Sswu::NU_ID);
+        for (message, (x, y)) in [
+            (
+                &b""[..],
+                (
+                    "0xf871caad25ea3b59c16cf87c1894902f7e7b2c822c3d3f73596c5ace8ddd14d1",
+                    "0x87b9ae23335bee057b99bac1e68588b18b5691af476234b8971bc4f011ddc99b",
+                ),
+            ),
+            (
+                b"abc",
+                (
+                    "0xfc3f5d734e8dce41ddac49f47dd2b8a57257522a865c124ed02b92b5237befa4",
+                    "0xfe4d197ecf5a62645b9690599e1d80e82c500b22ac705a0b421fac7b47157866",
+                ),
+            ),
+        ] {
+            let expected = Secp256r1Affine::from_xy(fe_from_str(x), fe_from_str(y)).unwrap();
+            let output = P256XmdSha256Sswu::encode_to_curve(message, dst.as_bytes());
+            assert_eq!(output.to_affine(), expected);
+        }
+    }
+
+    #[test]
+    fn test_hash_to_curve_suite() {
+        // Test vectors of https://datatracker.ietf.org/doc/html/rfc9380#appendix-J.1.1
+        let dst = format!("QUUX-V01-CS02-with-{}", P256XmdSha256Sswu::RO_ID);
+        for (message, (x, y)) in [
+            (
+                &b""[..],
+                (
+                    "0x2c15230b26dbc6fc9a37051158c95b79656e17a1a920b11394ca91c44247d3e4",
+                    "0x8a7a74985cc5c776cdfe4b1f19884970453912e9d31528c060be9ab5c43e8415",
+                ),
+            ),
+            (
+                b"abc",
+                (
+                    "0x0bb8b87485551aa43ed54f009230450b492fead5f1cc91658775dac4a3388a0f",
+                    "0x5c41b3d0731a27a7b14bc0bf0ccded2d8751f83493404c84a88e71ffd424212e",
+                ),
+            ),
+        ] {
+            let expected = Secp256r1Affine::from_xy(fe_from_str(x), fe_from_str(y)).unwrap();
+            let output = P256XmdSha256Sswu::hash_to_curve(message, dst.as_bytes());
+            assert_eq!(output.to_affine(), expected);
+        }
     }
 
     #[test]
@@ -1,8 +1,13 @@
+use crate::arithmetic::{mul_512, sbb, CurveEndo, EndoParameters};
+use crate::endo;
 use crate::ff::WithSmallOrderMulGroup;
 use crate::ff::{Field, PrimeField};
 use crate::group::Curve;
 use crate::group::{prime::PrimeCurveAffine, Group, GroupEncoding};
-use crate::hash_to_curve::svdw_hash_to_curve;
+use crate::hash_to_curve::{
+    svdw_encode_to_curve, svdw_hash_to_curve, svdw_hash_to_curve_with, EncodeToCurve,
+    ExpandMessage, HashToCurveWith,
+};
 use crate::secp256k1::{Fp, Fq};
 use crate::{
     impl_add_binop_specify_output, impl_binops_additive, impl_binops_additive_specify_output,
@@ -71,17 +76,54 @@ impl Secq256k1 {
     const SVDW_Z: Fq = Fq::ONE;
 }
 
+impl EncodeToCurve for Secq256k1 {
+    fn encode_to_curve<'a>(domain_prefix: &'a str) -> Box<dyn Fn(&[u8]) -> Self + 'a> {
+        svdw_encode_to_curve(Self::CURVE_ID, domain_prefix, Secq256k1::SVDW_Z)
+    }
+}
+
+impl HashToCurveWith for Secq256k1 {
+    fn hash_to_curve_with<'a, X: ExpandMessage + 'a>(
+        dst: &[u8],
+    ) -> Box<dyn Fn(&[u8]) -> Self + 'a> {
+        svdw_hash_to_curve_with::<Secq256k1, X, 64>(dst.to_vec(), Secq256k1::SVDW_Z)
+    }
+}
+
+// Short basis `(a1, -b1), (a2, b2)` of the lattice `{(x, y) : x + ZETA * y = 0 mod n}`
+// with `a1 * b2 + a2 * b1 = n`, where `ZETA` is `secq256k1::Fq::ZETA` and `n` the scalar field
+// modulus.
+// See https://github.com/demining/Endomorphism-Secp256k1/blob/main/README.md
+// to have more details about the endomorphism.
+const ENDO_PARAMS_SECQ256K1: EndoParameters = EndoParameters {
+    // round(2^256 * b1 / n)
+    gamma1: [0x6f547fa90abfe4c3, 0xe4437ed6010e8828, 0x0, 0x0],
+    // round(2^256 * b2 / n)
+    gamma2: [0xe86c90e49284eb16, 0x3086d221a7d46bcd, 0x0, 0x0],
+    b1: [0x6f547fa90abfe4c3, 0xe4437ed6010e8828, 0x0, 0x0],
+    b2: [0xe86c90e49284eb16, 0x3086d221a7d46bcd, 0x0, 0x0],
+};
+
+endo!(Secq256k1, Fp, ENDO_PARAMS_SECQ256K1);
+
 #[cfg(test)]
 mod tests {
+    use crate::arithmetic::CurveEndo;
     use crate::secq256k1::Fq;
     use crate::CurveExt;
-    use ff::WithSmallOrderMulGroup;
+    use ff::{Field, PrimeField, WithSmallOrderMulGroup};
 
     use super::Secq256k1;
 
     #[test]
     fn test_hash_to_curve() {
         crate::tests::curve::hash_to_curve_test::<Secq256k1>();
+        crate::tests::curve::hash_to_curve_with_test::<Secq256k1>("SVDW");
+    }
+
+    #[test]
+    fn test_encode_to_curve() {
+        crate::tests::curve::encode_to_curve_test::<Secq256k1>();
     }
 
     #[test]
@@ -95,6 +137,33 @@ mod tests {
         assert_eq!(g * Fq::ZETA, g.endo());
     }
 
+    #[test]
+    fn test_endo() {
+        use rand_core::OsRng;
+
+        for _ in 0..100000 {
+            let k = Fq::random(OsRng);
+            let (k1, k1_neg, k2, k2_neg) = Secq256k1::decompose_scalar(&k);
+            let (k1_neg, k2_neg) = (bool::from(k1_neg), bool::from(k2_neg));
+            if k1_neg & k2_neg {
+                assert_eq!(k, -Fq::from_u128(k1) + Fq::ZETA * Fq::from_u128(k2))
+            } else if k1_neg {
+                assert_eq!(k, -Fq::from_u128(k1) - Fq::ZETA * Fq::from_u128(k2))
+            } else if k2_neg {
+                assert_eq!(k, Fq::from_u128(k1) + Fq::ZETA * Fq::from_u128(k2))
+            } else {
+                assert_eq!(k, Fq::from_u128(k1) - Fq::ZETA * Fq::from_u128(k2))
+            }
+        }
+
+        crate::tests::curve::endo_decomposition_test::<Secq256k1>();
+    }
+
+    #[test]
+    fn test_glv_mul() {
+        crate::tests::curve::glv_mul_test::<Secq256k1>();
+    }
+
     #[test]
     fn test_serialization() {
         crate::tests::curve::random_serialization_test::<Secq256k1>();
@@ -1,13 +1,17 @@
 #![allow(clippy::eq_op)]
 
-use crate::ff::Field;
+use crate::ff::{Field, PrimeField};
 use crate::group::prime::PrimeCurveAffine;
+use crate::group::Group;
+use crate::hash_to_curve::{self, ElligatorSquared, EncodeToCurve, HashToCurveWith};
 use crate::legendre::Legendre;
 use crate::tests::fe_from_str;
 use crate::{group::GroupEncoding, serde::SerdeObject};
-use crate::{hash_to_curve, CurveAffine, CurveExt};
+use crate::{CurveAffine, CurveEndo, CurveExt};
+use ff::WithSmallOrderMulGroup;
 use rand_core::{OsRng, RngCore};
 use std::iter;
+use subtle::ConditionallySelectable;
 
 #[cfg(feature = "derive_serde")]
 use serde::{Deserialize, Serialize};
@@ -122,21 +126,15 @@ where
     }
 }
 
-fn is_on_curve<G: CurveExt>() {
-    assert!(bool::from(G::identity().is_on_curve()));
-    assert!(bool::from(G::generator().is_on_curve()));
-    assert!(bool::from(G::identity().is_on_curve()));
-    assert!(bool::from(G::generator().is_on_curve()));
-
-    for _ in 0..100 {
-        let point = G::random(OsRng);
-        assert!(bool::from(point.is_on_curve()));
-        let affine_point: G::AffineExt = point.into();
-        assert!(bool::from(affine_point.is_on_curve()));
-    }
+/// The tests of [`curve_tests`] that only need the group operations, for
+/// groups that are not curves such as `bn256::Gt`.
+pub fn group_tests<G: Group>() {
+    group_equality::<G>();
+    group_addition::<G>();
+    multiplication::<G>();
 }
 
-fn equality<G: CurveExt>() {
+fn group_equality<G: Group>() {
     let a = G::generator();
     let b = G::identity();
 
@@ -144,6 +142,8 @@ fn equality<G: CurveExt>() {
     assert!(b == b);
     assert!(a != b);
     assert!(b != a);
+    assert!(!bool::from(a.is_identity()));
+    assert!(bool::from(b.is_identity()));
 
     for _ in 0..100 {
         let a = G::random(OsRng);
@@ -153,9 +153,79 @@ fn equality<G: CurveExt>() {
         assert!(b == b);
         assert!(a != b);
         assert!(b != a);
+    }
+}
+
+fn group_addition<G: Group>() {
+    let a = G::identity();
+    let b = G::identity();
+    let c = a + b;
+    assert!(bool::from(c.is_identity()));
+    let c = a - b;
+    assert!(bool::from(c.is_identity()));
+
+    let a = G::identity();
+    let a = -a;
+    assert!(bool::from(a.is_identity()));
+
+    let a = G::random(OsRng);
+    assert!(a == a + G::identity());
+    assert!(a == G::identity() + a);
+    assert!(-a == G::identity() - a);
+
+    let a = G::identity();
+    let a = a.double();
+    assert!(bool::from(a.is_identity()));
+
+    let a = G::generator();
+    let a = a.double();
+    assert_eq!(a, G::generator() + G::generator());
 
-        let a: G::AffineExt = a.into();
-        let b: G::AffineExt = b.into();
+    let a = G::random(OsRng);
+    assert!(a.double() - a == a);
+
+    let a = G::random(OsRng);
+    let b = G::random(OsRng);
+    let c = G::random(OsRng);
+    assert!(a + b == b + a);
+    assert!(a - b == -(b - a));
+    assert!(c + (a + b) == a + (c + b));
+    assert!((a - b) - c == (a - c) - b);
+
+    let a = G::generator().double().double(); // 4P
+    let b = G::generator().double(); // 2P
+    let c = a + b;
+
+    let mut d = G::generator();
+    for _ in 0..5 {
+        d += G::generator();
+    }
+
+    assert!(c == d);
+    assert!(!bool::from(c.is_identity()));
+    assert!(!bool::from(d.is_identity()));
+}
+
+fn is_on_curve<G: CurveExt>() {
+    assert!(bool::from(G::identity().is_on_curve()));
+    assert!(bool::from(G::generator().is_on_curve()));
+    assert!(bool::from(G::identity().is_on_curve()));
+    assert!(bool::from(G::generator().is_on_curve()));
+
+    for _ in 0..100 {
+        let point = G::random(OsRng);
+        assert!(bool::from(point.is_on_curve()));
+        let affine_point: G::AffineExt = point.into();
+        assert!(bool::from(affine_point.is_on_curve()));
+    }
+}
+
+fn equality<G: CurveExt>() {
+    group_equality::<G>();
+
+    for _ in 0..100 {
+        let a: G::AffineExt = G::random(OsRng).into();
+        let b: G::AffineExt = G::random(OsRng).into();
 
         assert!(a == a);
         assert!(b == b);
@@ -222,6 +292,8 @@ fn projective_addition<G: CurveExt>() {
     assert!(a - b == -(b - a));
     assert!(c + (a + b) == a + (c + b));
     assert!((a - b) - c == (a - c) - b);
+    assert!(bool::from((a + b).is_on_curve()));
+    assert!(bool::from((a - b).is_on_curve()));
 
     let a = G::generator().double().double(); // 4P
     let b = G::generator().double(); // 2P
@@ -307,21 +379,34 @@ fn batch_normalize<G: CurveExt>() {
             }
         }
     }
+
+    // Large enough to take the parallel path with the `multicore` feature.
+    let a = G::random(OsRng);
+    let v = iter::successors(Some(a), |p| Some(*p + a))
+        .take(5000)
+        .enumerate()
+        .map(|(i, p)| if i % 7 == 0 { G::identity() } else { p })
+        .collect::<Vec<_>>();
+    let mut t = vec![G::AffineExt::identity(); v.len()];
+    G::batch_normalize(&v, &mut t);
+    for (p, q) in v.iter().zip(t.iter()) {
+        assert_eq!(p.to_affine(), *q);
+    }
 }
 
-fn multiplication<G: CurveExt>() {
+fn multiplication<G: Group>() {
     for _ in 1..1000 {
-        let s1 = G::ScalarExt::random(OsRng);
-        let s2 = G::ScalarExt::random(OsRng);
+        let s1 = G::Scalar::random(OsRng);
+        let s2 = G::Scalar::random(OsRng);
 
         let t0 = G::identity() * s1;
         assert!(bool::from(t0.is_identity()));
 
         let a = G::random(OsRng);
-        let t0 = a * G::ScalarExt::ONE;
+        let t0 = a * G::Scalar::ONE;
         assert_eq!(a, t0);
 
-        let t0 = a * G::ScalarExt::ZERO;
+        let t0 = a * G::Scalar::ZERO;
         assert!(bool::from(t0.is_identity()));
 
         let t0 = a * s1 + a * s2;
@@ -340,6 +425,71 @@ fn multiplication<G: CurveExt>() {
     }
 }
 
+/// Checks that the halves returned by `decompose_scalar` are below `2^128`
+/// before being cast to `u128`: each half is recomputed in the scalar field
+/// from `k` and the other half, so that a truncated half would show up as a
+/// value of more than 128 bits.
+pub fn endo_decomposition_test<G: CurveEndo>() {
+    let zeta_inv = G::ScalarExt::ZETA.invert().unwrap();
+    let below_2_128 = |e: &G::ScalarExt| e.to_repr().as_ref()[16..].iter().all(|b| *b == 0);
+
+    let edge_cases = [
+        G::ScalarExt::ZERO,
+        G::ScalarExt::ONE,
+        -G::ScalarExt::ONE,
+        G::ScalarExt::ZETA,
+        -G::ScalarExt::ZETA,
+        G::ScalarExt::from_u128(u128::MAX),
+        -G::ScalarExt::from_u128(u128::MAX),
+    ];
+    for k in edge_cases
+        .into_iter()
+        .chain(iter::repeat_with(|| G::ScalarExt::random(OsRng)).take(10000))
+    {
+        let (k1, k1_neg, k2, k2_neg) = G::decompose_scalar(&k);
+        let k1 = G::ScalarExt::from_u128(k1);
+        let k2 = G::ScalarExt::from_u128(k2);
+
+        // k = ±k1 + ZETA * t2 with t2 = k2 if k2_neg, -k2 otherwise
+        let t1 = G::ScalarExt::conditional_select(&k1, &-k1, k1_neg);
+        let t2 = G::ScalarExt::conditional_select(&-k2, &k2, k2_neg);
+        assert_eq!(k, t1 + G::ScalarExt::ZETA * t2);
+
+        let k1_full = k - G::ScalarExt::ZETA * t2;
+        let k1_full = G::ScalarExt::conditional_select(&k1_full, &-k1_full, k1_neg);
+        assert!(below_2_128(&k1_full));
+        assert_eq!(k1_full, k1);
+
+        let k2_full = (k - t1) * zeta_inv;
+        let k2_full = G::ScalarExt::conditional_select(&-k2_full, &k2_full, k2_neg);
+        assert!(below_2_128(&k2_full));
+        assert_eq!(k2_full, k2);
+    }
+}
+
+pub fn glv_mul_test<G: CurveEndo>() {
+    let a = G::random(OsRng);
+    assert_eq!(a.endo(), a * G::ScalarExt::ZETA);
+
+    let edge_cases = [
+        G::ScalarExt::ZERO,
+        G::ScalarExt::ONE,
+        -G::ScalarExt::ONE,
+        G::ScalarExt::ZETA,
+        -G::ScalarExt::ZETA,
+    ];
+    for s in edge_cases
+        .into_iter()
+        .chain(iter::repeat_with(|| G::ScalarExt::random(OsRng)).take(100))
+    {
+        let expected = a * s;
+        assert_eq!(a.mul_glv(&s), expected);
+        assert_eq!(a.mul_glv_vartime(&s), expected);
+        assert!(bool::from(G::identity().mul_glv(&s).is_identity()));
+        assert!(bool::from(G::identity().mul_glv_vartime(&s).is_identity()));
+    }
+}
+
 pub fn hash_to_curve_test<G: CurveExt>() {
     let hasher = G::hash_to_curve("test");
     let mut rng = OsRng;
@@ -352,6 +502,85 @@ pub fn hash_to_curve_test<G: CurveExt>() {
     }
 }
 
+pub fn hash_to_curve_with_test<G: HashToCurveWith>(method: &str) {
+    use hash_to_curve::{legacy_dst, ExpandMsgXmdBlake2b, ExpandMsgXof};
+    use sha3::Shake256;
+
+    let dst = legacy_dst("test", G::CURVE_ID, "XMD:BLAKE2b", method, "RO");
+    let blake2b = G::hash_to_curve_with::<ExpandMsgXmdBlake2b>(&dst);
+    let dst = legacy_dst("test", G::CURVE_ID, "XOF:SHAKE256", method, "RO");
+    let shake256 = G::hash_to_curve_with::<ExpandMsgXof<Shake256, 256>>(&dst);
+    let hasher = G::hash_to_curve("test");
+    let mut rng = OsRng;
+    for _ in 0..100 {
+        let message = iter::repeat_with(|| rng.next_u32().to_be_bytes())
+            .take(32)
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(blake2b(&message), hasher(&message));
+        let p = shake256(&message);
+        assert!(bool::from(p.is_on_curve()));
+        assert_ne!(p, hasher(&message));
+    }
+}
+
+pub fn encode_to_curve_test<G: EncodeToCurve>() {
+    let encoder = G::encode_to_curve("test");
+    let hasher = G::hash_to_curve("test");
+    let mut rng = OsRng;
+    for _ in 0..1000 {
+        let message = iter::repeat_with(|| rng.next_u32().to_be_bytes())
+            .take(32)
+            .flatten()
+            .collect::<Vec<_>>();
+        let p = encoder(&message);
+        assert!(bool::from(p.is_on_curve()));
+        assert_eq!(p, encoder(&message));
+        assert_ne!(p, hasher(&message));
+    }
+    assert_ne!(encoder(b"abc"), G::encode_to_curve("other")(b"abc"));
+}
+
+pub fn elligator_squared_test<G: ElligatorSquared>()
+where
+    G::Base: ff::FromUniformBytes<64>,
+{
+    let mut rng = OsRng;
+
+    // The inverse map finds `u` exactly once among the valid candidates,
+    // all of which are preimages.
+    for _ in 0..100 {
+        let u = G::Base::random(&mut rng);
+        let p = G::map_to_curve(u).unwrap();
+        let preimages = p
+            .map_to_curve_inverse()
+            .into_iter()
+            .filter_map(Option::from)
+            .collect::<Vec<G::Base>>();
+        assert_eq!(preimages.iter().filter(|v| **v == u).count(), 1);
+        for v in preimages {
+            assert_eq!(G::map_to_curve(v).unwrap(), p);
+        }
+    }
+    assert!(G::identity()
+        .map_to_curve_inverse()
+        .iter()
+        .all(|v| bool::from(v.is_none())));
+
+    let len = <G::Base as ff::PrimeField>::Repr::default().as_ref().len();
+    let mut top = 0;
+    for p in (0..32).map(|_| G::random(OsRng)).chain([G::identity()]) {
+        let bytes = p.encode_uniform(&mut rng);
+        assert_eq!(bytes.len(), 2 * len);
+        assert_eq!(G::decode_uniform(&bytes).unwrap(), p);
+        assert_ne!(bytes, p.encode_uniform(&mut rng));
+        top |= bytes[len - 1] | bytes[2 * len - 1];
+    }
+    // The encodings span the whole representation, not only the field.
+    assert!(top & 0x80 != 0);
+    assert!(G::decode_uniform(&[0; 3]).is_none());
+}
+
 pub fn svdw_map_to_curve_test<G: CurveExt>(
     z: G::Base,
     precomputed_constants: [&'static str; 4],
@@ -364,7 +593,8 @@ pub fn svdw_map_to_curve_test<G: CurveExt>(
     for (u, (x, y)) in test_vector.into_iter() {
         let u = fe_from_str(u);
         let expected = G::AffineExt::from_xy(fe_from_str(x), fe_from_str(y)).unwrap();
-        let output = hash_to_curve::svdw_map_to_curve::<G>(u, c1, c2, c3, c4, z).to_affine();
+        let output = hash_to_curve::svdw_map_to_curve::<G>(u, c1, c2, c3, c4, z).unwrap();
+        let output = output.to_affine();
         assert_eq!(output, expected);
     }
 }